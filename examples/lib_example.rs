@@ -55,8 +55,6 @@ pub(crate) async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///
 /// # Examples
 ///
-///
-
 /// Demonstrates language detection capabilities.
 pub(crate) async fn language_detection_example(
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -116,8 +114,6 @@ pub(crate) async fn language_detection_example(
 ///
 /// # Examples
 ///
-///
-
 /// Demonstrates translation capabilities.
 fn translation_example() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🦀 Translation Example");
@@ -0,0 +1,444 @@
+// Copyright © 2024 LangWeave. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Caching Language Detector
+//!
+//! This module provides a `CachingLanguageDetector` that wraps any
+//! `LanguageDetectorTrait` implementation and memoizes its results in a
+//! bounded least-recently-used cache.
+//!
+//! How cache keys are derived from the input is pluggable through
+//! `CacheKeyStrategy`: the full text can be used as the key, or a compact
+//! prefix or content hash when inputs are large documents.
+//!
+//! ## Examples
+//!
+//! ```
+//! use langweave::caching_detector::{CacheKeyStrategy, CachingLanguageDetector};
+//! use langweave::language_detector::LanguageDetector;
+//! use langweave::language_detector_trait::LanguageDetectorTrait;
+//!
+//! let detector = CachingLanguageDetector::new(LanguageDetector::new(), 128)
+//!     .with_key_strategy(CacheKeyStrategy::ContentHash);
+//!
+//! assert_eq!(detector.detect("Bonjour le monde").unwrap(), "fr");
+//! // The second call is answered from the cache.
+//! assert_eq!(detector.detect("Bonjour le monde").unwrap(), "fr");
+//! assert_eq!(detector.len(), 1);
+//! ```
+
+use crate::error::I18nError;
+use crate::language_detector::LanguageDetector;
+use crate::language_detector_trait::LanguageDetectorTrait;
+use crate::{check_detected, is_strict};
+use async_trait::async_trait;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The strategy used to derive a cache key from the input text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKeyStrategy {
+    /// Use the full text as the key.
+    ///
+    /// Exact, but memory grows with the size of the inputs.
+    FullText,
+    /// Use the first `n` bytes of the text together with its length.
+    ///
+    /// Compact, but different texts sharing a prefix and length collide.
+    Prefix(usize),
+    /// Use a 64-bit hash of the whole text together with its length.
+    ///
+    /// Compact and examines the whole text; collisions are possible but
+    /// extremely unlikely.
+    ContentHash,
+}
+
+/// A snapshot of a `CachingLanguageDetector`'s cache counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of detections answered from the cache.
+    pub hits: u64,
+    /// The number of detections delegated to the inner detector.
+    pub misses: u64,
+    /// The maximum number of cached results.
+    pub capacity: usize,
+}
+
+/// A cache key derived from input text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Text(String),
+    Prefix(String, usize),
+    Hash(u64, usize),
+}
+
+impl CacheKeyStrategy {
+    /// Derives the cache key for `text` under this strategy.
+    fn key(self, text: &str) -> CacheKey {
+        match self {
+            CacheKeyStrategy::FullText => {
+                CacheKey::Text(text.to_string())
+            }
+            CacheKeyStrategy::Prefix(n) => {
+                let mut end = n.min(text.len());
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                CacheKey::Prefix(text[..end].to_string(), text.len())
+            }
+            CacheKeyStrategy::ContentHash => {
+                let mut hasher = DefaultHasher::new();
+                text.hash(&mut hasher);
+                CacheKey::Hash(hasher.finish(), text.len())
+            }
+        }
+    }
+}
+
+/// A cache key together with the strict mode setting the result was
+/// computed under.
+type StrictKey = (bool, CacheKey);
+
+/// A bounded least-recently-used map of detection results.
+#[derive(Debug, Default)]
+struct LruCache {
+    entries: HashMap<StrictKey, Result<String, I18nError>>,
+    order: VecDeque<StrictKey>,
+}
+
+impl LruCache {
+    /// Returns the cached result for `key`, marking it most recently used.
+    fn get(
+        &mut self,
+        key: &StrictKey,
+    ) -> Option<Result<String, I18nError>> {
+        let result = self.entries.get(key)?.clone();
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            if let Some(k) = self.order.remove(pos) {
+                self.order.push_back(k);
+            }
+        }
+        Some(result)
+    }
+
+    /// Stores a result, evicting the least recently used entries beyond
+    /// `capacity`.
+    fn insert(
+        &mut self,
+        key: StrictKey,
+        result: Result<String, I18nError>,
+        capacity: usize,
+    ) {
+        if capacity == 0 {
+            return;
+        }
+        if self.entries.insert(key.clone(), result).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                let _ = self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// A language detector that caches the results of an inner detector.
+///
+/// Results are cached separately with strict mode on and off, and every
+/// result goes through the strict check, so toggling `set_strict` never
+/// returns a language computed under the other setting.
+#[derive(Debug)]
+pub struct CachingLanguageDetector<D = LanguageDetector> {
+    inner: D,
+    capacity: usize,
+    strategy: CacheKeyStrategy,
+    cache: Mutex<LruCache>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<D: LanguageDetectorTrait> CachingLanguageDetector<D> {
+    /// Creates a new `CachingLanguageDetector` around `inner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The detector whose results are cached.
+    /// * `capacity` - The maximum number of cached results; `0` disables caching.
+    ///
+    /// # Returns
+    ///
+    /// * `CachingLanguageDetector<D>` - A detector keyed on the full text by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::caching_detector::CachingLanguageDetector;
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = CachingLanguageDetector::new(LanguageDetector::new(), 64);
+    /// assert!(detector.is_empty());
+    /// ```
+    pub fn new(inner: D, capacity: usize) -> Self {
+        CachingLanguageDetector {
+            inner,
+            capacity,
+            strategy: CacheKeyStrategy::FullText,
+            cache: Mutex::new(LruCache::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets how cache keys are derived from the input text.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The key derivation strategy.
+    ///
+    /// # Returns
+    ///
+    /// * `CachingLanguageDetector<D>` - The detector with the strategy applied.
+    #[must_use]
+    pub fn with_key_strategy(
+        mut self,
+        strategy: CacheKeyStrategy,
+    ) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns `true` if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cache's hit and miss counters and its capacity.
+    ///
+    /// The counters are updated atomically and can be read from any
+    /// thread while detections are running.
+    ///
+    /// # Returns
+    ///
+    /// * `CacheStats` - A snapshot of the counters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::caching_detector::CachingLanguageDetector;
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let detector = CachingLanguageDetector::new(LanguageDetector::new(), 16);
+    /// let _ = detector.detect("Hallo Welt");
+    /// let _ = detector.detect("Hallo Welt");
+    ///
+    /// let stats = detector.stats();
+    /// assert_eq!((stats.hits, stats.misses, stats.capacity), (1, 1, 16));
+    /// ```
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            capacity: self.capacity,
+        }
+    }
+
+    /// Removes every cached result.
+    pub fn clear(&self) {
+        *self.lock() = LruCache::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the cached result for `key`, recording a hit or a miss.
+    fn cached(
+        &self,
+        key: &StrictKey,
+    ) -> Option<Result<String, I18nError>> {
+        let result = self.lock().get(key);
+        let counter = if result.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        let _ = counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+}
+
+#[async_trait]
+impl<D: LanguageDetectorTrait> LanguageDetectorTrait
+    for CachingLanguageDetector<D>
+{
+    fn detect(&self, text: &str) -> Result<String, I18nError> {
+        let key = (is_strict(), self.strategy.key(text));
+        if let Some(result) = self.cached(&key) {
+            return result.and_then(check_detected);
+        }
+
+        let result = self.inner.detect(text).and_then(check_detected);
+        self.lock().insert(key, result.clone(), self.capacity);
+        result
+    }
+
+    async fn detect_async(
+        &self,
+        text: &str,
+    ) -> Result<String, I18nError> {
+        let key = (is_strict(), self.strategy.key(text));
+        if let Some(result) = self.cached(&key) {
+            return result.and_then(check_detected);
+        }
+
+        let result = self
+            .inner
+            .detect_async(text)
+            .await
+            .and_then(check_detected);
+        self.lock().insert(key, result.clone(), self.capacity);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct CountingDetector {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LanguageDetectorTrait for CountingDetector {
+        fn detect(&self, text: &str) -> Result<String, I18nError> {
+            let _ = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(text.chars().take(2).collect())
+        }
+
+        async fn detect_async(
+            &self,
+            text: &str,
+        ) -> Result<String, I18nError> {
+            self.detect(text)
+        }
+    }
+
+    #[test]
+    fn test_cache_hits_skip_inner_detector() {
+        let detector = CachingLanguageDetector::new(
+            CountingDetector::default(),
+            8,
+        );
+        assert_eq!(detector.detect("fr text").unwrap(), "fr");
+        assert_eq!(detector.detect("fr text").unwrap(), "fr");
+        assert_eq!(detector.inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(detector.len(), 1);
+
+        detector.clear();
+        assert!(detector.is_empty());
+    }
+
+    #[test]
+    fn test_stats() {
+        let detector = CachingLanguageDetector::new(
+            CountingDetector::default(),
+            4,
+        );
+        let _ = detector.detect("aa");
+        let _ = detector.detect("aa");
+        let _ = detector.detect("bb");
+        let _ = detector.detect("aa");
+        assert_eq!(
+            detector.stats(),
+            CacheStats {
+                hits: 2,
+                misses: 2,
+                capacity: 4,
+            }
+        );
+
+        // Clearing the cache keeps the counters.
+        detector.clear();
+        let _ = detector.detect("aa");
+        assert_eq!(detector.stats().misses, 3);
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let detector = CachingLanguageDetector::new(
+            CountingDetector::default(),
+            2,
+        );
+        let _ = detector.detect("aa");
+        let _ = detector.detect("bb");
+        let _ = detector.detect("aa");
+        let _ = detector.detect("cc");
+        assert_eq!(detector.len(), 2);
+
+        // "bb" was least recently used and has been evicted.
+        let _ = detector.detect("aa");
+        assert_eq!(detector.inner.calls.load(Ordering::SeqCst), 3);
+        let _ = detector.detect("bb");
+        assert_eq!(detector.inner.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let detector = CachingLanguageDetector::new(
+            CountingDetector::default(),
+            0,
+        );
+        let _ = detector.detect("aa");
+        let _ = detector.detect("aa");
+        assert!(detector.is_empty());
+        assert_eq!(detector.inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_key_strategies() {
+        let text = "héllo wörld";
+        assert_eq!(
+            CacheKeyStrategy::FullText.key(text),
+            CacheKey::Text(text.to_string())
+        );
+        // The prefix never splits a multi-byte character.
+        assert_eq!(
+            CacheKeyStrategy::Prefix(2).key(text),
+            CacheKey::Prefix("h".to_string(), text.len())
+        );
+        assert_eq!(
+            CacheKeyStrategy::ContentHash.key(text),
+            CacheKeyStrategy::ContentHash.key(text)
+        );
+        assert_ne!(
+            CacheKeyStrategy::ContentHash.key(text),
+            CacheKeyStrategy::ContentHash.key("hello world")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_detection_is_cached() {
+        let detector =
+            CachingLanguageDetector::new(LanguageDetector::new(), 4)
+                .with_key_strategy(CacheKeyStrategy::ContentHash);
+        assert_eq!(
+            detector.detect_async("Le chat").await.unwrap(),
+            "fr"
+        );
+        assert_eq!(detector.detect("Le chat").unwrap(), "fr");
+        assert_eq!(detector.len(), 1);
+    }
+}
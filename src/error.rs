@@ -1,10 +1,57 @@
 // Copyright © 2024 LangWeave. All rights reserved.
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
 use thiserror::Error;
 
+/// The underlying cause of an `I18nError`.
+///
+/// The cause is shared so errors stay cheap to clone, and two sources
+/// compare equal when their messages are equal. It dereferences to the
+/// wrapped error, which `Error::source` returns as is so callers can
+/// downcast it.
+#[derive(Clone)]
+pub struct ErrorSource(Arc<dyn StdError + Send + Sync>);
+
+impl Deref for ErrorSource {
+    type Target = dyn StdError + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Eq for ErrorSource {}
+
 /// Represents errors that can occur during internationalization and translation operations.
+///
+/// With the `serde` feature enabled, errors implement `Serialize` and
+/// `Deserialize` so they can cross JSON APIs and worker channels.
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum I18nError {
     /// Indicates that the language detection process failed.
@@ -27,12 +74,78 @@ pub enum I18nError {
     #[error("Unsupported language: {0}")]
     UnsupportedLanguage(String),
 
+    /// Indicates that a batch of texts is written in more than one language.
+    ///
+    /// This error lists the distinct languages found, in order of first
+    /// appearance.
+    #[error("Inconsistent languages: {}", .0.join(", "))]
+    InconsistentLanguages(Vec<String>),
+
+    /// Indicates that translation catalogs could not be loaded.
+    ///
+    /// This error occurs when the locales directory is missing or a
+    /// catalog file cannot be read or parsed.
+    #[error("Failed to load translations: {message}")]
+    LoadFailed {
+        /// What failed to load, such as the path of a catalog.
+        message: String,
+        /// The error that caused the failure, if any. It is not
+        /// serialized.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        source: Option<ErrorSource>,
+    },
+
     /// Represents any other unexpected errors that may occur during library operations.
     #[error("An unexpected error occurred: {0}")]
     UnexpectedError(String),
 }
 
 impl I18nError {
+    /// Creates an `I18nError::LoadFailed` without an underlying cause.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::error::I18nError;
+    /// use std::error::Error;
+    ///
+    /// let error = I18nError::load_failed("locales/fr.po");
+    /// assert_eq!(error.to_string(), "Failed to load translations: locales/fr.po");
+    /// assert!(error.source().is_none());
+    /// ```
+    pub fn load_failed(message: impl Into<String>) -> Self {
+        I18nError::LoadFailed {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Creates an `I18nError::LoadFailed` caused by another error, which
+    /// `Error::source` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::error::I18nError;
+    /// use std::error::Error;
+    /// use std::io;
+    ///
+    /// let cause = io::Error::new(io::ErrorKind::NotFound, "no such file");
+    /// let error = I18nError::load_failed_with_source("locales/fr.po", cause);
+    /// let source = error.source().unwrap();
+    /// assert_eq!(source.to_string(), "no such file");
+    /// assert!(source.downcast_ref::<io::Error>().is_some());
+    /// ```
+    pub fn load_failed_with_source(
+        message: impl Into<String>,
+        source: impl StdError + Send + Sync + 'static,
+    ) -> Self {
+        I18nError::LoadFailed {
+            message: message.into(),
+            source: Some(ErrorSource(Arc::new(source))),
+        }
+    }
+
     /// Returns a string slice describing the error.
     pub fn as_str(&self) -> &str {
         match self {
@@ -41,6 +154,10 @@ pub fn as_str(&self) -> &str {
             }
             I18nError::TranslationFailed(_) => "translation failed",
             I18nError::UnsupportedLanguage(_) => "unsupported language",
+            I18nError::InconsistentLanguages(_) => {
+                "inconsistent languages"
+            }
+            I18nError::LoadFailed { .. } => "load failed",
             I18nError::UnexpectedError(_) => "unexpected error",
         }
     }
@@ -50,6 +167,35 @@ pub fn as_str(&self) -> &str {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let errors = vec![
+            I18nError::LanguageDetectionFailed,
+            I18nError::TranslationFailed("fr:Hello".to_string()),
+            I18nError::UnsupportedLanguage("xx".to_string()),
+            I18nError::InconsistentLanguages(vec![
+                "en".to_string(),
+                "fr".to_string(),
+            ]),
+            I18nError::load_failed("locales/fr.po"),
+            I18nError::UnexpectedError("boom".to_string()),
+        ];
+        for error in errors {
+            let json = serde_json::to_string(&error).unwrap();
+            let decoded: I18nError =
+                serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, error);
+        }
+        assert_eq!(
+            serde_json::to_string(&I18nError::UnsupportedLanguage(
+                "xx".to_string()
+            ))
+            .unwrap(),
+            r#"{"UnsupportedLanguage":"xx"}"#
+        );
+    }
+
     #[test]
     fn test_error_messages() {
         assert_eq!(
@@ -66,6 +212,18 @@ fn test_error_messages() {
                 .to_string(),
             "Unsupported language: xyz"
         );
+        assert_eq!(
+            I18nError::InconsistentLanguages(vec![
+                "en".to_string(),
+                "fr".to_string()
+            ])
+            .to_string(),
+            "Inconsistent languages: en, fr"
+        );
+        assert_eq!(
+            I18nError::load_failed("locales").to_string(),
+            "Failed to load translations: locales"
+        );
         assert_eq!(
             I18nError::UnexpectedError("test error".to_string())
                 .to_string(),
@@ -87,6 +245,14 @@ fn test_as_str() {
             I18nError::UnsupportedLanguage("xyz".to_string()).as_str(),
             "unsupported language"
         );
+        assert_eq!(
+            I18nError::InconsistentLanguages(Vec::new()).as_str(),
+            "inconsistent languages"
+        );
+        assert_eq!(
+            I18nError::load_failed("locales").as_str(),
+            "load failed"
+        );
         assert_eq!(
             I18nError::UnexpectedError("test error".to_string())
                 .as_str(),
@@ -186,6 +352,34 @@ fn test_error_partial_eq() {
         assert!(error1 != error3);
     }
 
+    #[test]
+    fn test_error_source() {
+        use std::io;
+
+        assert!(I18nError::LanguageDetectionFailed.source().is_none());
+        assert!(I18nError::load_failed("locales").source().is_none());
+
+        let cause = io::Error::new(io::ErrorKind::InvalidData, "bad");
+        let error = I18nError::load_failed_with_source("fr.po", cause);
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<io::Error>().unwrap().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let cloned = error.clone();
+        assert_eq!(cloned, error);
+        assert_ne!(
+            cloned,
+            I18nError::load_failed_with_source(
+                "fr.po",
+                io::Error::new(io::ErrorKind::InvalidData, "worse")
+            )
+        );
+        assert_ne!(error, I18nError::load_failed("fr.po"));
+        assert!(format!("{:?}", error).contains("InvalidData"));
+    }
+
     #[test]
     fn test_error_non_exhaustive() {
         // This test demonstrates that we can match on all current variants
@@ -196,6 +390,8 @@ fn use_error(error: I18nError) {
                 I18nError::LanguageDetectionFailed => {}
                 I18nError::TranslationFailed(_) => {}
                 I18nError::UnsupportedLanguage(_) => {}
+                I18nError::InconsistentLanguages(_) => {}
+                I18nError::LoadFailed { .. } => {}
                 I18nError::UnexpectedError(_) => {}
             }
         }
@@ -211,6 +407,7 @@ fn test_error_variants() {
             I18nError::LanguageDetectionFailed,
             I18nError::TranslationFailed("test".to_string()),
             I18nError::UnsupportedLanguage("en".to_string()),
+            I18nError::load_failed("locales"),
             I18nError::UnexpectedError("oops".to_string()),
         ];
 
@@ -219,6 +416,8 @@ fn test_error_variants() {
                 I18nError::LanguageDetectionFailed => {}
                 I18nError::TranslationFailed(_) => {}
                 I18nError::UnsupportedLanguage(_) => {}
+                I18nError::InconsistentLanguages(_) => {}
+                I18nError::LoadFailed { .. } => {}
                 I18nError::UnexpectedError(_) => {}
             }
         }
@@ -57,77 +57,462 @@
 
 use crate::error::I18nError;
 use crate::language_detector_trait::LanguageDetectorTrait;
+use crate::script::Script;
+use crate::{check_detected, sanitize_input, strict_rejects};
 use async_trait::async_trait;
 use log::{debug, error};
 use once_cell::sync::Lazy;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{BufRead, Read};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::task;
-use whatlang::{detect, Lang};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+use whatlang::{detect, Info, Lang};
+
+/// The default `whatlang` confidence above which a statistical detection is
+/// trusted.
+const STATISTICAL_CONFIDENCE: f64 = 0.3;
+
+/// The process-wide confidence threshold, stored as `f64` bits, used by
+/// detectors without their own threshold.
+static DEFAULT_CONFIDENCE_THRESHOLD: Lazy<AtomicU64> =
+    Lazy::new(|| AtomicU64::new(STATISTICAL_CONFIDENCE.to_bits()));
+
+/// The `whatlang` confidence required when the text has fewer words than
+/// the configured minimum for statistical detection.
+const SHORT_TEXT_CONFIDENCE: f64 = 0.9;
+
+/// The default limit, in bytes, on the compiled size of a pattern.
+const DEFAULT_PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+/// The default limit, in bytes, on the lazy DFA cache of a pattern.
+const DEFAULT_PATTERN_DFA_SIZE_LIMIT: usize = 1 << 20;
+
+/// The scripts covered by the built-in detection patterns.
+const SUPPORTED_SCRIPTS: &[Script] = &[
+    Script::Latin,
+    Script::Cyrillic,
+    Script::Arabic,
+    Script::Hebrew,
+    Script::Hiragana,
+    Script::Katakana,
+    Script::Han,
+    Script::Devanagari,
+    Script::Hangul,
+];
 
 /// A thread-safe struct for detecting the language of a given text.
 #[derive(Debug, Clone)]
 pub struct LanguageDetector {
     patterns: Arc<Vec<(Regex, &'static str)>>,
+    neutral_symbols: bool,
+    min_words_for_statistical: usize,
+    pipeline: Arc<Vec<Stage>>,
+    require_content_word: bool,
+    pattern_limits: (usize, usize),
+    max_prefix: Option<usize>,
+    confidence_threshold: Option<f64>,
+    reconcile: bool,
+    min_text_length: usize,
 }
 
-/// A static list of language detection patterns for common languages.
-static PATTERNS: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
-    vec![
-        // English
-        (
-            Regex::new(r"(?i)\b(hello|hi|hey|goodbye|bye|thank you|thanks|please|the|a|an|in|on|at|for|to|of)\b").expect("Failed to compile English regex"),
-            "en",
-        ),
-        // French
-        (
-            Regex::new(r"(?i)\b(bonjour|salut|au revoir|merci|s'il vous plaît|le|la|les|un|une|des|dans|sur|pour|de)\b").expect("Failed to compile French regex"),
-            "fr",
-        ),
-        // German
-        (
-            Regex::new(r"(?i)\b(hallo|guten tag|auf wiedersehen|tschüss|danke|bitte|der|die|das|ein|eine|in|auf|für|zu|von)\b").expect("Failed to compile German regex"),
-            "de",
-        ),
-        // Spanish
-        (
-            Regex::new(r"(?i)\b(hola|adiós|gracias|por favor|el|la|los|las|un|una|unos|unas|en|para|por)\b").expect("Failed to compile Spanish regex"),
-            "es",
-        ),
-        // Portuguese
-        (
-            Regex::new(r"(?i)\b(olá|adeus|obrigado|obrigada|por favor|o|a|os|as|um|uma|uns|umas|em|para|por)\b").expect("Failed to compile Portuguese regex"),
-            "pt",
-        ),
-        // Russian (includes Cyrillic script detection)
-        (
-            Regex::new(r"(?i)\b(здравствуйте|привет|до свидания|пока|спасибо|пожалуйста)|[\p{Cyrillic}]+").expect("Failed to compile Russian regex"),
-            "ru",
-        ),
-        // Arabic script detection
-        (Regex::new(r"[\p{Arabic}]+").expect("Failed to compile Arabic regex"), "ar"),
-        // Japanese (prioritize Hiragana and Katakana)
-        (
-            Regex::new(r"(?i)\b(こんにちは|さようなら|ありがとう|お願いします)|[\p{Hiragana}\p{Katakana}ー]+").expect("Failed to compile Japanese regex"),
-            "ja",
-        ),
-        // Chinese (Han script detection, but exclude Japanese-specific characters)
-        (
-            Regex::new(r"(?i)\b(你好|再见|谢谢|请)|(?:[\p{Han}&&[^\p{Hiragana}\p{Katakana}ー]]+)").expect("Failed to compile Chinese regex"),
-            "zh",
-        ),
-        // Hindi (includes Devanagari script detection)
-        (
-            Regex::new(r"(?i)\b(नमस्ते|अलविदा|धन्यवाद|कृपया)|[\p{Devanagari}]+").expect("Failed to compile Hindi regex"),
-            "hi",
-        ),
-        // Korean (includes Hangul script detection)
-        (
-            Regex::new(r"(?i)\b(안녕하세요|안녕히 가세요|감사합니다|주세요)|[\p{Hangul}]+").expect("Failed to compile Korean regex"),
-            "ko",
-        ),
-    ]
-});
+/// A step of the detection pipeline configured with
+/// `LanguageDetector::with_pipeline`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Matches the built-in keyword and script regular expressions.
+    KeywordRegex,
+    /// Decides from the script when the text is overwhelmingly written in
+    /// one non-Latin script.
+    ScriptMatch,
+    /// Runs `whatlang` on the whole text.
+    WhatlangWhole,
+    /// Runs `whatlang` word by word, accepting the first confident result.
+    WhatlangWords,
+}
+
+/// The format of language codes returned by `LanguageDetector::detect_as`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LangCodeFormat {
+    /// Two-letter ISO 639-1 codes (e.g., "en"), as returned by `detect`.
+    Iso6391,
+    /// Three-letter ISO 639-3 codes (e.g., "eng").
+    Iso6393,
+    /// BCP 47 tags with the most common region (e.g., "en-US").
+    Bcp47,
+}
+
+/// The ISO 639-1 code, ISO 639-3 code and BCP 47 tag of each language
+/// with a two-letter code.
+const LANG_CODES: &[(&str, &str, &str)] = &[
+    ("en", "eng", "en-US"),
+    ("fr", "fra", "fr-FR"),
+    ("de", "deu", "de-DE"),
+    ("es", "spa", "es-ES"),
+    ("pt", "por", "pt-BR"),
+    ("ja", "jpn", "ja-JP"),
+    ("zh", "cmn", "zh-CN"),
+    ("ar", "ara", "ar-SA"),
+    ("hi", "hin", "hi-IN"),
+    ("ko", "kor", "ko-KR"),
+    ("ru", "rus", "ru-RU"),
+    ("it", "ita", "it-IT"),
+    ("nl", "nld", "nl-NL"),
+    ("he", "heb", "he-IL"),
+    ("id", "ind", "id-ID"),
+];
+
+/// The stages run by default, in order.
+const DEFAULT_PIPELINE: &[Stage] = &[
+    Stage::ScriptMatch,
+    Stage::KeywordRegex,
+    Stage::WhatlangWords,
+];
+
+/// Common characters written differently in Simplified and Traditional
+/// Chinese, as `(simplified, traditional)` pairs.
+const CHINESE_VARIANT_PAIRS: &[(char, char)] = &[
+    ('这', '這'),
+    ('个', '個'),
+    ('们', '們'),
+    ('来', '來'),
+    ('说', '說'),
+    ('时', '時'),
+    ('为', '為'),
+    ('会', '會'),
+    ('对', '對'),
+    ('学', '學'),
+    ('发', '發'),
+    ('经', '經'),
+    ('问', '問'),
+    ('动', '動'),
+    ('国', '國'),
+    ('过', '過'),
+    ('还', '還'),
+    ('实', '實'),
+    ('现', '現'),
+    ('长', '長'),
+    ('门', '門'),
+    ('马', '馬'),
+    ('车', '車'),
+    ('东', '東'),
+    ('书', '書'),
+    ('见', '見'),
+    ('话', '話'),
+    ('让', '讓'),
+    ('认', '認'),
+    ('识', '識'),
+    ('边', '邊'),
+    ('进', '進'),
+    ('运', '運'),
+    ('开', '開'),
+    ('关', '關'),
+    ('头', '頭'),
+    ('买', '買'),
+    ('卖', '賣'),
+    ('爱', '愛'),
+    ('电', '電'),
+    ('气', '氣'),
+    ('语', '語'),
+    ('汉', '漢'),
+    ('写', '寫'),
+    ('鸟', '鳥'),
+    ('鱼', '魚'),
+    ('谢', '謝'),
+    ('请', '請'),
+    ('欢', '歡'),
+];
+
+/// The written variant of a Chinese text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChineseVariant {
+    /// Simplified Chinese (`zh-Hans`).
+    Simplified,
+    /// Traditional Chinese (`zh-Hant`).
+    Traditional,
+    /// Han text without conclusive evidence for either variant.
+    Ambiguous,
+}
+
+/// A coarse confidence level for a detection result.
+///
+/// The levels are derived from the numeric confidence of a
+/// `DetectionResult`: `High` from 0.75, `Medium` from 0.4, and `Low` below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Less than 40% of the words support the language.
+    Low,
+    /// Between 40% and 75% of the words support the language.
+    Medium,
+    /// At least 75% of the words support the language.
+    High,
+}
+
+impl Confidence {
+    /// Maps a numeric confidence between `0.0` and `1.0` to its level.
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - The numeric confidence.
+    ///
+    /// # Returns
+    ///
+    /// * `Confidence` - The corresponding level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::Confidence;
+    ///
+    /// assert_eq!(Confidence::from_score(0.9), Confidence::High);
+    /// assert_eq!(Confidence::from_score(0.5), Confidence::Medium);
+    /// assert_eq!(Confidence::from_score(0.1), Confidence::Low);
+    /// ```
+    pub fn from_score(score: f64) -> Self {
+        if score >= 0.75 {
+            Confidence::High
+        } else if score >= 0.4 {
+            Confidence::Medium
+        } else {
+            Confidence::Low
+        }
+    }
+}
+
+/// A keyword or script run matched by a detection pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordMatch {
+    /// The matched substring (e.g., "bonjour").
+    pub keyword: String,
+    /// The byte range of the match within the input text.
+    pub range: Range<usize>,
+}
+
+/// An account of how a language was detected, returned by
+/// `LanguageDetector::explain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The detected language code.
+    pub language: String,
+    /// The pipeline stage that identified the language.
+    pub stage: Stage,
+    /// The pattern match that triggered the result, when the keyword or
+    /// script stage decided.
+    pub matched: Option<KeywordMatch>,
+}
+
+/// How a language was identified, as reported by
+/// `LanguageDetector::detect_explained`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetectionMethod {
+    /// A keyword or script pattern matched.
+    Pattern,
+    /// `whatlang` classified the text statistically.
+    Whatlang,
+}
+
+/// A detected language with the span that triggered it, returned by
+/// `LanguageDetector::detect_explained`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    /// The detected language code.
+    pub code: String,
+    /// The method that identified the language.
+    pub method: DetectionMethod,
+    /// The byte range of the pattern match within the input text, for
+    /// pattern detections.
+    pub range: Option<Range<usize>>,
+}
+
+/// A reason to treat a detection result with caution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetectionWarning {
+    /// The text has fewer than three words.
+    TooShort,
+    /// The text mixes several writing systems.
+    MixedScripts,
+    /// Less than 40% of the words support the detected language.
+    LowConfidence,
+    /// No pattern matched, so the language was guessed word by word.
+    WordByWordFallbackUsed,
+}
+
+/// The number of words below which a text is considered too short for
+/// reliable detection.
+const MIN_RELIABLE_WORDS: usize = 3;
+
+/// The maximum number of blocking tasks `detect_batch_async` runs at once.
+const MAX_BATCH_TASKS: usize = 4;
+
+/// The largest share of a batch that may be undetectable before
+/// `detect_consistent` gives up.
+const MAX_UNDETECTABLE_SHARE: f64 = 0.25;
+
+/// The number of bytes `detect_reader` reads between detection attempts.
+const STREAM_CHUNK_BYTES: u64 = 4 * 1024;
+
+/// The largest prefix of a document `detect_reader` keeps in memory.
+const STREAM_MAX_BYTES: usize = 64 * 1024;
+
+/// A span of text written in a single language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The byte range of the span within the analysed text.
+    pub range: Range<usize>,
+    /// The detected language code of the span.
+    pub language: String,
+}
+
+thread_local! {
+    /// The detector shared by `LanguageDetector::with_thread_local` calls
+    /// on the current thread.
+    static THREAD_DETECTOR: LanguageDetector = LanguageDetector::new();
+}
+
+/// The outcome of a language detection, with supporting details.
+///
+/// The `Display` implementation renders a one-line summary such as
+/// `fr (87% confidence, Latin script)`; the alternate form (`{:#}`) renders
+/// one field per line for verbose output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectionResult {
+    /// The detected language code (e.g., "fr").
+    pub language: String,
+    /// The share of the text's words supporting the language, between
+    /// `0.0` and `1.0`.
+    pub confidence: f64,
+    /// The dominant script of the text, if any.
+    pub script: Option<Script>,
+}
+
+impl fmt::Display for DetectionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = (self.confidence * 100.0).round();
+        if f.alternate() {
+            writeln!(f, "language:   {}", self.language)?;
+            write!(f, "confidence: {}%", percent)?;
+            if let Some(script) = self.script {
+                write!(f, "\nscript:     {}", script)?;
+            }
+            Ok(())
+        } else {
+            write!(f, "{} ({}% confidence", self.language, percent)?;
+            if let Some(script) = self.script {
+                write!(f, ", {} script", script)?;
+            }
+            f.write_str(")")
+        }
+    }
+}
+
+/// Precision and recall for one language in an `EvalReport`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanguageMetrics {
+    /// The share of texts predicted as this language that were labeled
+    /// with it, or `0.0` if it was never predicted.
+    pub precision: f64,
+    /// The share of texts labeled with this language that were predicted
+    /// as it, or `0.0` if it never appears as a label.
+    pub recall: f64,
+}
+
+/// The accuracy of a detector on a labeled data set, returned by
+/// `LanguageDetector::evaluate`.
+///
+/// Texts whose language cannot be detected are predicted as `und`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EvalReport {
+    /// The share of texts whose predicted language matches the label.
+    pub accuracy: f64,
+    /// Precision and recall for every language seen as a label or a
+    /// prediction.
+    pub per_language: HashMap<String, LanguageMetrics>,
+    /// The number of texts for each `(true, predicted)` language pair.
+    pub confusion: HashMap<(String, String), usize>,
+}
+
+/// The sources of the built-in detection patterns, with the language each
+/// identifies, in the order they are tried.
+const PATTERN_SOURCES: &[(&str, &str)] = &[
+    // English
+    (
+        r"(?i)\b(hello|hi|hey|goodbye|bye|thank you|thanks|please|the|a|an|in|on|at|for|to|of)\b",
+        "en",
+    ),
+    // French
+    (
+        r"(?i)\b(bonjour|salut|au revoir|merci|s'il vous plaît|le|la|les|un|une|des|dans|sur|pour|de)\b",
+        "fr",
+    ),
+    // German
+    (
+        r"(?i)\b(hallo|guten tag|auf wiedersehen|tschüss|danke|bitte|der|die|das|ein|eine|in|auf|für|zu|von)\b",
+        "de",
+    ),
+    // Spanish
+    (
+        r"(?i)\b(hola|adiós|gracias|por favor|el|la|los|las|un|una|unos|unas|en|para|por)\b",
+        "es",
+    ),
+    // Portuguese
+    (
+        r"(?i)\b(olá|adeus|obrigado|obrigada|por favor|o|a|os|as|um|uma|uns|umas|em|para|por)\b",
+        "pt",
+    ),
+    // Russian (includes Cyrillic script detection)
+    (
+        r"(?i)\b(здравствуйте|привет|до свидания|пока|спасибо|пожалуйста)|[\p{Cyrillic}]+",
+        "ru",
+    ),
+    // Arabic script detection
+    (r"[\p{Arabic}]+", "ar"),
+    // Japanese (prioritize Hiragana and Katakana)
+    (
+        r"(?i)\b(こんにちは|さようなら|ありがとう|お願いします)|[\p{Hiragana}\p{Katakana}ー]+",
+        "ja",
+    ),
+    // Chinese (Han script detection, but exclude Japanese-specific characters)
+    (
+        r"(?i)\b(你好|再见|谢谢|请)|(?:[\p{Han}&&[^\p{Hiragana}\p{Katakana}ー]]+)",
+        "zh",
+    ),
+    // Hindi (includes Devanagari script detection)
+    (r"(?i)\b(नमस्ते|अलविदा|धन्यवाद|कृपया)|[\p{Devanagari}]+", "hi"),
+    // Korean (includes Hangul script detection)
+    (
+        r"(?i)\b(안녕하세요|안녕히 가세요|감사합니다|주세요)|[\p{Hangul}]+",
+        "ko",
+    ),
+    // Italian
+    (
+        r"(?i)\b(ciao|buongiorno|arrivederci|grazie|per favore|prego|gli|della|delle|sono|questo|questa|molto|anche|perché)\b",
+        "it",
+    ),
+    // Dutch
+    (
+        r"(?i)\b(goedemorgen|tot ziens|dank je|dank u|alstublieft|hoe|het|een|niet|zijn|ik|wij|voor|maar|ook)\b",
+        "nl",
+    ),
+    // Hebrew (includes Hebrew script detection)
+    (r"(?i)\b(שלום|להתראות|תודה|בבקשה)|[\p{Hebrew}]+", "he"),
+    // Indonesian
+    (
+        r"(?i)\b(halo|selamat pagi|selamat tinggal|terima kasih|tolong|silakan|yang|dan|ini|itu|dengan|tidak|saya|untuk|adalah)\b",
+        "id",
+    ),
+];
+
+/// The built-in detection patterns, compiled on first use.
+static PATTERNS: Lazy<
+    Result<Vec<(Regex, &'static str)>, regex::Error>,
+> = Lazy::new(builtin_patterns);
 
 impl LanguageDetector {
     /// Creates a new instance of `LanguageDetector`.
@@ -150,70 +535,141 @@ impl LanguageDetector {
     /// assert_eq!(detector.convert_lang_code(Lang::Fra), "fr");
     /// assert_eq!(detector.convert_lang_code(Lang::Deu), "de");
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a built-in pattern fails to compile. Use `try_new` where
+    /// a panic is not acceptable.
     #[must_use]
     pub fn new() -> Self {
-        LanguageDetector {
-            patterns: Arc::new(PATTERNS.clone()),
+        match LanguageDetector::try_new() {
+            Ok(detector) => detector,
+            Err(e) => panic!("{}", e),
         }
     }
 
-    /// Converts `whatlang`'s language codes to the desired format.
+    /// Creates a new instance of `LanguageDetector`, returning an error
+    /// instead of panicking if a built-in pattern fails to compile.
     ///
-    /// This function maps `whatlang`'s internal `Lang` enum values to their ISO 639-1
-    /// equivalents or other common language codes used by the application.
+    /// The built-in patterns are compiled once, on first use, and the
+    /// outcome is shared by every later call.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<LanguageDetector, I18nError>` - A new instance of the `LanguageDetector` struct, or an error if the patterns are invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let detector = LanguageDetector::try_new().unwrap();
+    /// assert_eq!(detector.detect("Bonjour le monde").unwrap(), "fr");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::UnexpectedError` if a
+    /// built-in pattern fails to compile.
+    pub fn try_new() -> Result<Self, I18nError> {
+        let patterns = PATTERNS.as_ref().map_err(|e| {
+            I18nError::UnexpectedError(format!(
+                "Invalid built-in pattern: {}",
+                e
+            ))
+        })?;
+        Ok(LanguageDetector {
+            patterns: Arc::new(patterns.clone()),
+            neutral_symbols: false,
+            min_words_for_statistical: 0,
+            pipeline: Arc::new(DEFAULT_PIPELINE.to_vec()),
+            require_content_word: false,
+            pattern_limits: (
+                DEFAULT_PATTERN_SIZE_LIMIT,
+                DEFAULT_PATTERN_DFA_SIZE_LIMIT,
+            ),
+            max_prefix: None,
+            confidence_threshold: None,
+            reconcile: false,
+            min_text_length: 0,
+        })
+    }
+
+    /// Sets whether emoji and symbol characters are treated as neutral.
+    ///
+    /// When enabled, emoji, pictographs, dingbats and their modifiers are
+    /// stripped from the input before detection, so only linguistic
+    /// tokens are considered. Input made up entirely of such symbols
+    /// fails with `I18nError::LanguageDetectionFailed`.
     ///
     /// # Arguments
     ///
-    /// * `lang` - The `Lang` enum from `whatlang`.
+    /// * `enabled` - Whether to strip emoji and symbols before detection.
     ///
     /// # Returns
     ///
-    /// * `String` - The standardized language code (e.g., "en", "fr").
+    /// * `LanguageDetector` - The detector with the option applied.
     ///
     /// # Examples
     ///
     /// ```
     /// use langweave::language_detector::LanguageDetector;
-    /// use whatlang::Lang;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
     ///
-    /// let detector = LanguageDetector::new();
-    /// assert_eq!(detector.convert_lang_code(Lang::Eng), "en");
-    /// assert_eq!(detector.convert_lang_code(Lang::Fra), "fr");
-    /// assert_eq!(detector.convert_lang_code(Lang::Deu), "de");
+    /// let detector = LanguageDetector::new().with_neutral_symbols(true);
+    /// assert_eq!(detector.detect("🎉🎉 Bonjour 🎉").unwrap(), "fr");
+    /// assert!(detector.detect("🎉🎉🎉").is_err());
     /// ```
-    pub fn convert_lang_code(&self, lang: Lang) -> String {
-        match lang {
-            Lang::Eng => "en",
-            Lang::Fra => "fr",
-            Lang::Deu => "de",
-            Lang::Spa => "es",
-            Lang::Por => "pt",
-            Lang::Jpn => "ja",
-            Lang::Cmn => "zh",
-            Lang::Ara => "ar",
-            Lang::Hin => "hi",
-            Lang::Kor => "ko",
-            Lang::Rus => "ru",
-            _ => lang.code(),
-        }
-        .to_string()
+    #[must_use]
+    pub fn with_neutral_symbols(mut self, enabled: bool) -> Self {
+        self.neutral_symbols = enabled;
+        self
     }
-}
 
-#[async_trait]
-impl LanguageDetectorTrait for LanguageDetector {
-    /// Detects the language of the given text synchronously.
+    /// Returns the built-in detection patterns as data.
     ///
-    /// This method first attempts to detect the language using custom regular expression patterns
-    /// for common words. If no match is found, it falls back to `whatlang` for statistical detection.
+    /// Each entry pairs a language code with the raw source of the regular
+    /// expression used to recognise it, in the order the patterns are tried.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(String, String)>` - The `(language code, regex source)` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let sources = LanguageDetector::pattern_sources();
+    /// let (lang, source) = &sources[0];
+    /// assert_eq!(lang, "en");
+    /// assert!(source.contains("hello"));
+    /// ```
+    pub fn pattern_sources() -> Vec<(String, String)> {
+        PATTERN_SOURCES
+            .iter()
+            .map(|(source, lang)| {
+                (lang.to_string(), source.to_string())
+            })
+            .collect()
+    }
+
+    /// Sets the minimum word count before `whatlang` results are trusted.
+    ///
+    /// `whatlang` is unreliable on one or two words. When the text has fewer
+    /// words than this minimum, a statistical detection is only accepted if
+    /// `whatlang` reports it as reliable with a confidence of at least 0.9;
+    /// otherwise detection fails rather than guessing. Keyword and script
+    /// patterns are unaffected. The default of `0` disables the check.
     ///
     /// # Arguments
     ///
-    /// * `text` - A string slice that holds the text to analyze.
+    /// * `min_words` - The minimum number of words for regular statistical detection.
     ///
     /// # Returns
     ///
-    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+    /// * `LanguageDetector` - The detector with the option applied.
     ///
     /// # Examples
     ///
@@ -221,129 +677,137 @@ impl LanguageDetectorTrait for LanguageDetector {
     /// use langweave::language_detector::LanguageDetector;
     /// use langweave::language_detector_trait::LanguageDetectorTrait;
     ///
-    /// let detector = LanguageDetector::new();
+    /// let detector = LanguageDetector::new().with_min_words_for_statistical(3);
+    /// // Keyword patterns still apply to short text.
+    /// assert_eq!(detector.detect("Bonjour").unwrap(), "fr");
+    /// ```
+    #[must_use]
+    pub fn with_min_words_for_statistical(
+        mut self,
+        min_words: usize,
+    ) -> Self {
+        self.min_words_for_statistical = min_words;
+        self
+    }
+
+    /// Requires a content word before trusting keyword patterns.
     ///
-    /// // Detect English
-    /// let result = detector.detect("The quick brown fox");
-    /// assert_eq!(result.unwrap(), "en");
+    /// The keyword patterns consist mostly of stopwords ("the", "le",
+    /// "der"), so they fire on text made only of glue words. When enabled,
+    /// the keyword stage is skipped unless at least one Latin-script word
+    /// is not itself a keyword, so stopword-only input falls through to
+    /// the statistical stages or fails. Disabled by default.
     ///
-    /// // Detect French
-    /// let result = detector.detect("Le chat noir");
-    /// assert_eq!(result.unwrap(), "fr");
+    /// # Arguments
     ///
-    /// // Detect German
-    /// let result = detector.detect("Der schnelle braune Fuchs");
-    /// assert_eq!(result.unwrap(), "de");
-    /// ```
+    /// * `require` - Whether keyword matches need a content word.
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// This function will return an `I18nError::LanguageDetectionFailed` if:
-    /// - The input text is empty or contains only non-alphabetic characters.
-    /// - The language detection process fails to identify a language with sufficient confidence.
-    fn detect(&self, text: &str) -> Result<String, I18nError> {
-        let normalized_text = text.trim();
-
-        // Reject empty or non-alphabetic input
-        if normalized_text.is_empty()
-            || !normalized_text.chars().any(|c| c.is_alphabetic())
-        {
-            error!("Empty or non-alphabetic input: {}", text);
-            return Err(I18nError::LanguageDetectionFailed);
-        }
-
-        // Try custom patterns first
-        for (pattern, lang) in self.patterns.iter() {
-            if pattern.is_match(normalized_text) {
-                debug!("Custom heuristic matched pattern for language '{}'", lang);
-                return Ok(lang.to_string());
-            }
-        }
-
-        // If custom heuristics fail, detect word-by-word using `whatlang`
-        for word in normalized_text.split_whitespace() {
-            if let Some(info) = detect(word) {
-                if info.is_reliable() || info.confidence() > 0.3 {
-                    debug!(
-                        "Detected language '{}' for word '{}'",
-                        info.lang(),
-                        word
-                    );
-                    return Ok(self.convert_lang_code(info.lang()));
-                }
-            }
-        }
-
-        // If no detections succeed, return an error
-        error!("Failed to detect language for text: {}", text);
-        Err(I18nError::LanguageDetectionFailed)
+    /// * `LanguageDetector` - The detector with the option applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{LanguageDetector, Stage};
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let detector = LanguageDetector::new()
+    ///     .with_require_content_word(true)
+    ///     .with_pipeline(&[Stage::KeywordRegex]);
+    /// assert!(detector.detect("the a an of").is_err());
+    /// assert_eq!(detector.detect("the weather").unwrap(), "en");
+    /// ```
+    #[must_use]
+    pub fn with_require_content_word(mut self, require: bool) -> Self {
+        self.require_content_word = require;
+        self
     }
 
-    /// Detects the language of the given text asynchronously.
+    /// Sets the `whatlang` confidence above which a statistical result is
+    /// accepted by this detector.
     ///
-    /// This method provides the same functionality as `detect`, but operates asynchronously,
-    /// allowing for non-blocking language detection in concurrent contexts.
+    /// Results that `whatlang` reports as reliable are always accepted.
+    /// Without this option the detector follows the process-wide default
+    /// set by `set_default_confidence_threshold`. The value is clamped to
+    /// the range `0.0..=1.0`.
     ///
     /// # Arguments
     ///
-    /// * `text` - A string slice that holds the text to analyze.
+    /// * `threshold` - The minimum confidence, between `0.0` and `1.0`.
     ///
     /// # Returns
     ///
-    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+    /// * `LanguageDetector` - The detector with the threshold applied.
     ///
     /// # Examples
     ///
     /// ```
     /// use langweave::language_detector::LanguageDetector;
-    /// use langweave::language_detector_trait::LanguageDetectorTrait;
     ///
-    /// #[tokio::main]
-    /// async fn main() {
-    ///     let detector = LanguageDetector::new();
+    /// let detector = LanguageDetector::new().with_confidence_threshold(0.5);
+    /// assert_eq!(detector.confidence_threshold(), 0.5);
+    /// ```
+    #[must_use]
+    pub fn with_confidence_threshold(mut self, threshold: f64) -> Self {
+        self.confidence_threshold = Some(threshold.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Sets the process-wide default confidence threshold.
     ///
-    ///     // Detect Spanish asynchronously
-    ///     let result = detector.detect_async("El gato negro").await;
-    ///     assert_eq!(result.unwrap(), "es");
+    /// The default applies to every detector without its own
+    /// `with_confidence_threshold`, including the shared detector behind
+    /// the crate-level `detect_language`. It starts at `0.3` and is clamped
+    /// to the range `0.0..=1.0`.
     ///
-    ///     // Detect Portuguese asynchronously
-    ///     let result = detector.detect_async("O gato preto").await;
-    ///     assert_eq!(result.unwrap(), "pt");
-    /// }
-    /// ```
+    /// # Arguments
     ///
-    /// # Errors
+    /// * `threshold` - The minimum confidence, between `0.0` and `1.0`.
     ///
-    /// This function will return an `I18nError::LanguageDetectionFailed` if:
-    /// - The input text is empty or contains only non-alphabetic characters.
-    /// - The language detection process fails to identify a language with sufficient confidence.
-    async fn detect_async(
-        &self,
-        text: &str,
-    ) -> Result<String, I18nError> {
-        let text = text.to_string();
-        let patterns = Arc::clone(&self.patterns);
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// LanguageDetector::set_default_confidence_threshold(0.6);
+    /// assert_eq!(LanguageDetector::new().confidence_threshold(), 0.6);
+    /// ```
+    pub fn set_default_confidence_threshold(threshold: f64) {
+        DEFAULT_CONFIDENCE_THRESHOLD.store(
+            threshold.clamp(0.0, 1.0).to_bits(),
+            Ordering::SeqCst,
+        );
+    }
 
-        task::spawn_blocking(move || {
-            let detector = LanguageDetector { patterns };
-            detector.detect(&text)
+    /// Returns the confidence threshold this detector applies.
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - The detector's own threshold, or the process-wide default.
+    pub fn confidence_threshold(&self) -> f64 {
+        self.confidence_threshold.unwrap_or_else(|| {
+            f64::from_bits(
+                DEFAULT_CONFIDENCE_THRESHOLD.load(Ordering::SeqCst),
+            )
         })
-        .await
-        .map_err(|e| {
-            error!("Async language detection task failed: {:?}", e);
-            I18nError::LanguageDetectionFailed
-        })?
     }
-}
 
-impl Default for LanguageDetector {
-    /// Provides a default instance of `LanguageDetector`.
+    /// Caps how much of the input detection examines.
     ///
-    /// # Returns
+    /// Only the first `max_chars` characters are analysed, which bounds the
+    /// cost of detecting large documents. The cut is moved forward to the
+    /// end of the grapheme cluster it falls in, so combining marks, emoji
+    /// modifiers and zero-width-joiner sequences are never split. No cap
+    /// is applied by default.
     ///
-    /// * `LanguageDetector` - A default instance using predefined patterns for common languages.
+    /// # Arguments
     ///
-    /// This method allows the `LanguageDetector` to be initialized easily with default patterns.
+    /// * `max_chars` - The number of characters to examine.
+    ///
+    /// # Returns
+    ///
+    /// * `LanguageDetector` - The detector with the cap applied.
     ///
     /// # Examples
     ///
@@ -351,189 +815,3765 @@ impl Default for LanguageDetector {
     /// use langweave::language_detector::LanguageDetector;
     /// use langweave::language_detector_trait::LanguageDetectorTrait;
     ///
-    /// let detector = LanguageDetector::default();
-    /// let result = detector.detect("Hello, world!");
-    /// assert_eq!(result.unwrap(), "en");
+    /// let detector = LanguageDetector::new().with_max_prefix(12);
+    /// let text = "Le chat noir. The quick brown fox jumps over the lazy dog.";
+    /// assert_eq!(detector.detect(text).unwrap(), "fr");
     /// ```
-    fn default() -> Self {
-        Self::new()
+    #[must_use]
+    pub fn with_max_prefix(mut self, max_chars: usize) -> Self {
+        self.max_prefix = Some(max_chars);
+        self
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::Arc;
 
-    #[test]
+    /// Lets a confident `whatlang` result override a weak keyword match.
+    ///
+    /// Keyword patterns can fire on a single word shared with another
+    /// language. When enabled and a keyword match disagrees with a reliable
+    /// `whatlang` detection of the whole text, the statistical result wins
+    /// if its confidence exceeds the share of words supporting the keyword
+    /// language, and is reported as `Stage::WhatlangWhole`. Disabled by
+    /// default, in which case the keyword result always stands.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to reconcile keyword and statistical results.
+    ///
+    /// # Returns
+    ///
+    /// * `LanguageDetector` - The detector with the option applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let text = "la casa es muy grande y bonita, y tiene un jardín con muchas flores";
+    /// let detector = LanguageDetector::new().with_reconciliation(true);
+    /// assert_eq!(detector.detect(text).unwrap(), "es");
+    /// ```
+    #[must_use]
+    pub fn with_reconciliation(mut self, enabled: bool) -> Self {
+        self.reconcile = enabled;
+        self
+    }
+
+    /// Sets the minimum length, in characters, of the text accepted by
+    /// `detect_strict`.
+    ///
+    /// Surrounding whitespace is not counted. The default of 0 accepts
+    /// any input; other detection methods ignore this setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_chars` - The minimum number of characters.
+    ///
+    /// # Returns
+    ///
+    /// * `LanguageDetector` - The detector with the minimum length applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new().with_min_text_length(10);
+    /// assert!(detector.detect_strict("  Hi  ").is_err());
+    /// ```
+    #[must_use]
+    pub fn with_min_text_length(mut self, min_chars: usize) -> Self {
+        self.min_text_length = min_chars;
+        self
+    }
+
+    /// Sets the size limits used to compile custom patterns.
+    ///
+    /// `size_limit` bounds the compiled program of a pattern and
+    /// `dfa_size_limit` the lazy DFA cache used while matching it (see
+    /// `regex::RegexBuilder`). Patterns exceeding `size_limit` are rejected
+    /// by `with_custom_pattern`. Both default to 1 MiB. Only patterns added
+    /// afterwards are affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `size_limit` - The maximum compiled size of a pattern, in bytes.
+    /// * `dfa_size_limit` - The maximum DFA cache size of a pattern, in bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `LanguageDetector` - The detector with the limits applied.
+    #[must_use]
+    pub fn with_pattern_limits(
+        mut self,
+        size_limit: usize,
+        dfa_size_limit: usize,
+    ) -> Self {
+        self.pattern_limits = (size_limit, dfa_size_limit);
+        self
+    }
+
+    /// Adds a custom detection pattern, tried before the built-in ones.
+    ///
+    /// The pattern is compiled with the limits set by
+    /// `with_pattern_limits`. Matching time is always linear in the input
+    /// because the `regex` crate never backtracks, and the limits bound the
+    /// memory a pathological pattern can use, so no detection call can hang
+    /// or exhaust memory on crafted input.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regular expression to match.
+    /// * `lang` - The language code returned when the pattern matches.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<LanguageDetector, I18nError>` - The detector with the pattern added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let detector = LanguageDetector::new()
+    ///     .with_custom_pattern(r"(?i)\b(ciao|grazie)\b", "it")
+    ///     .unwrap();
+    /// assert_eq!(detector.detect("Ciao a tutti").unwrap(), "it");
+    ///
+    /// let tiny = LanguageDetector::new().with_pattern_limits(64, 64);
+    /// assert!(tiny.with_custom_pattern(r"\w{100}", "xx").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::UnexpectedError` if the
+    /// pattern is invalid or exceeds the size limit.
+    pub fn with_custom_pattern(
+        mut self,
+        pattern: &str,
+        lang: &'static str,
+    ) -> Result<Self, I18nError> {
+        let regex = self.compile_custom(pattern)?;
+        Arc::make_mut(&mut self.patterns).insert(0, (regex, lang));
+        Ok(self)
+    }
+
+    /// Appends a detection pattern, tried after the existing ones.
+    ///
+    /// The pattern is compiled with the limits set by
+    /// `with_pattern_limits`. Patterns are shared between clones until one
+    /// of them changes, so clones made before this call are unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex` - The regular expression to match.
+    /// * `lang` - The language code returned when the pattern matches.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), I18nError>` - `Ok(())` if the pattern was added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let mut detector = LanguageDetector::new();
+    /// detector.add_pattern(r"[\u{0E00}-\u{0E7F}]", "th").unwrap();
+    /// assert_eq!(detector.detect("สวัสดีครับ").unwrap(), "th");
+    /// assert!(detector.add_pattern("(", "xx").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::UnexpectedError` if the
+    /// pattern is invalid or exceeds the size limit.
+    pub fn add_pattern(
+        &mut self,
+        regex: &str,
+        lang: &'static str,
+    ) -> Result<(), I18nError> {
+        let compiled = self.compile_custom(regex)?;
+        Arc::make_mut(&mut self.patterns).push((compiled, lang));
+        Ok(())
+    }
+
+    /// Compiles a user-supplied pattern with the configured size limits.
+    fn compile_custom(
+        &self,
+        pattern: &str,
+    ) -> Result<Regex, I18nError> {
+        let (size_limit, dfa_size_limit) = self.pattern_limits;
+        RegexBuilder::new(pattern)
+            .size_limit(size_limit)
+            .dfa_size_limit(dfa_size_limit)
+            .build()
+            .map_err(|e| {
+                I18nError::UnexpectedError(format!(
+                    "Invalid pattern '{}': {}",
+                    pattern, e
+                ))
+            })
+    }
+
+    /// Sets which detection stages run, and in what order.
+    ///
+    /// Stages are tried in the given order until one identifies a language.
+    /// The default pipeline is `ScriptMatch`, `KeywordRegex`,
+    /// `WhatlangWords`; leaving out the `whatlang` stages restricts
+    /// detection to the built-in patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `stages` - The stages to run, in order.
+    ///
+    /// # Returns
+    ///
+    /// * `LanguageDetector` - The detector with the pipeline applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{LanguageDetector, Stage};
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let detector = LanguageDetector::new()
+    ///     .with_pipeline(&[Stage::KeywordRegex, Stage::ScriptMatch]);
+    /// assert_eq!(detector.detect("Bonjour").unwrap(), "fr");
+    /// // Without the `whatlang` stages, unknown words are not guessed.
+    /// assert!(detector.detect("Schmetterling").is_err());
+    /// ```
+    #[must_use]
+    pub fn with_pipeline(mut self, stages: &[Stage]) -> Self {
+        self.pipeline = Arc::new(stages.to_vec());
+        self
+    }
+
+    /// Detects the language of the given text, ruling out some languages.
+    ///
+    /// Patterns for excluded languages are skipped and `whatlang` results
+    /// that map to them are discarded, so the best remaining candidate is
+    /// returned. This is useful when certain languages are known to be
+    /// impossible for an input and would only produce false positives.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    /// * `excluded` - The language codes that must not be returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// // "la" would otherwise match the French pattern first.
+    /// assert_eq!(detector.detect_excluding("la casa", &["fr"]).unwrap(), "es");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed` if:
+    /// - The input text is empty or contains only non-alphabetic characters.
+    /// - No language outside the excluded set can be identified.
+    pub fn detect_excluding(
+        &self,
+        text: &str,
+        excluded: &[&str],
+    ) -> Result<String, I18nError> {
+        self.detect_staged(text, excluded).map(|(lang, _)| lang)
+    }
+
+    /// Runs the detection pipeline, returning the detected language along
+    /// with the stage that identified it.
+    fn detect_staged(
+        &self,
+        text: &str,
+        excluded: &[&str],
+    ) -> Result<(String, Stage), I18nError> {
+        self.detect_prepared(&self.prepare(text)?, excluded)
+    }
+
+    /// Runs the detection pipeline on text already cleaned by `prepare`.
+    fn detect_prepared(
+        &self,
+        normalized_text: &str,
+        excluded: &[&str],
+    ) -> Result<(String, Stage), I18nError> {
+        let word_count = normalized_text.split_whitespace().count();
+
+        for &stage in self.pipeline.iter() {
+            let detected = match stage {
+                // Text written almost entirely in one non-Latin script
+                // needs no further analysis
+                Stage::ScriptMatch => script_language(normalized_text)
+                    .filter(|lang| !excluded.contains(lang))
+                    .map(str::to_string),
+                Stage::KeywordRegex
+                    if self.require_content_word
+                        && !self.has_content_word(normalized_text) =>
+                {
+                    None
+                }
+                Stage::KeywordRegex => self
+                    .match_patterns(normalized_text, excluded)
+                    .map(str::to_string),
+                Stage::WhatlangWhole => self.match_whole(
+                    normalized_text,
+                    word_count,
+                    excluded,
+                ),
+                Stage::WhatlangWords => self.match_statistical(
+                    normalized_text,
+                    word_count,
+                    excluded,
+                ),
+            };
+            if let Some(lang) = detected {
+                debug!(
+                    "Stage {:?} detected language '{}'",
+                    stage, lang
+                );
+                if stage == Stage::KeywordRegex {
+                    if let Some(statistical) =
+                        self.reconcile(normalized_text, &lang, excluded)
+                    {
+                        return Ok((
+                            check_detected(statistical)?,
+                            Stage::WhatlangWhole,
+                        ));
+                    }
+                }
+                return Ok((check_detected(lang)?, stage));
+            }
+        }
+
+        // If no detections succeed, return an error
+        error!(
+            "Failed to detect language for text: {}",
+            normalized_text
+        );
+        Err(I18nError::LanguageDetectionFailed)
+    }
+
+    /// Detects the language after folding diacritics to their base letters.
+    ///
+    /// The text is decomposed (NFD), every nonspacing mark (`\p{Mn}`) is
+    /// removed and the rest is recomposed, so "é", "ș" and "ế" all become
+    /// their base letter. Letters without a canonical decomposition, such
+    /// as "ß", "ł" or "ø", are kept. Marks in other scripts are stripped
+    /// too, such as Arabic vowel signs or Japanese dakuten. The language
+    /// detected from the folded text is returned. Folding is opt-in
+    /// because keywords such as "für" or "olá" no longer match once
+    /// folded.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// // "e" followed by a combining acute accent
+    /// let text = "Le cafe\u{301} est de\u{301}licieux";
+    /// assert_eq!(detector.detect_normalized(text).unwrap(), "fr");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as `detect`.
+    pub fn detect_normalized(
+        &self,
+        text: &str,
+    ) -> Result<String, I18nError> {
+        self.detect_excluding(&fold_diacritics(text), &[])
+    }
+
+    /// Detects the language, delegating to a fallback when detection fails.
+    ///
+    /// The closure is only called with the original text if detection
+    /// returns `I18nError::LanguageDetectionFailed`, so an expensive
+    /// fallback (such as a remote model) is used only for hard inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    /// * `f` - The fallback detector.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code, or the fallback's result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let lang = detector.detect_or_else("42", |_| Ok("und".to_string()));
+    /// assert_eq!(lang.unwrap(), "und");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the fallback's error if detection fails and
+    /// the fallback fails too.
+    pub fn detect_or_else(
+        &self,
+        text: &str,
+        f: impl FnOnce(&str) -> Result<String, I18nError>,
+    ) -> Result<String, I18nError> {
+        match self.detect_excluding(text, &[]) {
+            Err(I18nError::LanguageDetectionFailed) => f(text),
+            result => result,
+        }
+    }
+
+    /// Quantifies how strongly the text is dominated by a single language.
+    ///
+    /// Every word is attributed to a language using the custom patterns or,
+    /// failing that, `whatlang`. The score is the share of attributed words
+    /// that belong to the most frequent language, so `1.0` means the text is
+    /// purely one language and lower values indicate mixing.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<f64, I18nError>` - A score between `0.0` and `1.0`, or an error if no word could be attributed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// assert_eq!(detector.monolingual_score("hello the world").unwrap(), 1.0);
+    /// assert!(detector.monolingual_score("hello bonjour").unwrap() < 1.0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed` if
+    /// the input is empty, non-alphabetic, or no word could be attributed to
+    /// a language.
+    pub fn monolingual_score(
+        &self,
+        text: &str,
+    ) -> Result<f64, I18nError> {
+        self.score_languages(text)?
+            .first()
+            .map(|(_, score)| *score)
+            .ok_or(I18nError::LanguageDetectionFailed)
+    }
+
+    /// Detects the top language together with its margin over the runner-up.
+    ///
+    /// The margin is the difference between the word shares of the two most
+    /// likely languages (see `monolingual_score`). A small margin signals
+    /// that a closely related language was almost as likely, so the result
+    /// should not be trusted too much; a text attributed to a single
+    /// language has a margin equal to its top share.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(String, f64), I18nError>` - The top language code and its margin between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let (lang, margin) = detector.detect_with_margin("the cat and the dog").unwrap();
+    /// assert_eq!(lang, "en");
+    /// assert!(margin > 0.0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed` if
+    /// the input is empty, non-alphabetic, or no word could be attributed to
+    /// a language.
+    pub fn detect_with_margin(
+        &self,
+        text: &str,
+    ) -> Result<(String, f64), I18nError> {
+        let mut scores = self.score_languages(text)?.into_iter();
+        let (lang, top) =
+            scores.next().ok_or(I18nError::LanguageDetectionFailed)?;
+        let runner_up = scores.next().map_or(0.0, |(_, score)| score);
+        Ok((lang, top - runner_up))
+    }
+
+    /// Detects the language together with its confidence and script.
+    ///
+    /// The language is the one returned by `detect`; the confidence is the
+    /// share of the text's words attributed to it (see
+    /// `monolingual_score`).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DetectionResult, I18nError>` - The detection result if successful, or an error if detection fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let result = detector.detect_with_confidence("Le chat et la maison").unwrap();
+    /// assert_eq!(result.to_string(), "fr (100% confidence, Latin script)");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as `detect`.
+    pub fn detect_with_confidence(
+        &self,
+        text: &str,
+    ) -> Result<DetectionResult, I18nError> {
+        let normalized_text = self.prepare(text)?;
+        let (language, _) =
+            self.detect_prepared(&normalized_text, &[])?;
+        let confidence = self.share_of(&normalized_text, &language);
+
+        Ok(DetectionResult {
+            language,
+            confidence,
+            script: Script::dominant(text),
+        })
+    }
+
+    /// Detects the language and explains which stage and match decided it.
+    ///
+    /// When the keyword stage identifies the language, the first substring
+    /// matched by the winning pattern is reported with its byte range, so
+    /// it is possible to audit that, say, "bonjour" at offset 0 triggered a
+    /// French classification. When the script stage decides, the first run
+    /// of the language's script is reported instead. `whatlang` results
+    /// carry no match. Only the text detection examined is searched, so a
+    /// match never extends past the `with_max_prefix` cut, and its range
+    /// points into the original input.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Explanation, I18nError>` - The detected language with the evidence behind it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{LanguageDetector, Stage};
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let explanation = detector.explain("Bonjour mes amis").unwrap();
+    /// assert_eq!(explanation.language, "fr");
+    /// assert_eq!(explanation.stage, Stage::KeywordRegex);
+    ///
+    /// let matched = explanation.matched.unwrap();
+    /// assert_eq!(matched.keyword, "Bonjour");
+    /// assert_eq!(matched.range, 0..7);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as `detect`.
+    pub fn explain(
+        &self,
+        text: &str,
+    ) -> Result<Explanation, I18nError> {
+        let normalized_text = self.prepare(text)?;
+        let (language, stage) =
+            self.detect_prepared(&normalized_text, &[])?;
+        let matched = if stage == Stage::KeywordRegex
+            || stage == Stage::ScriptMatch
+        {
+            self.patterns
+                .iter()
+                .filter(|(_, lang)| *lang == language)
+                .find_map(|(pattern, _)| pattern.find(&normalized_text))
+                .map(|m| {
+                    let range =
+                        input_range(text, &normalized_text, m.range());
+                    KeywordMatch {
+                        keyword: text[range.clone()].to_string(),
+                        range,
+                    }
+                })
+        } else {
+            None
+        };
+
+        Ok(Explanation {
+            language,
+            stage,
+            matched,
+        })
+    }
+
+    /// Detects the language and reports the method and span behind it.
+    ///
+    /// This is a condensed form of `explain`: keyword and script stages
+    /// are reported as `DetectionMethod::Pattern` with the byte range of
+    /// the first match, and `whatlang` stages as
+    /// `DetectionMethod::Whatlang` without a range.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Detection, I18nError>` - The detected language code with the method and matched range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{DetectionMethod, LanguageDetector};
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let detection = detector.detect_explained("Bonjour mes amis").unwrap();
+    /// assert_eq!(detection.code, "fr");
+    /// assert_eq!(detection.method, DetectionMethod::Pattern);
+    /// assert_eq!(detection.range, Some(0..7));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as `detect`.
+    pub fn detect_explained(
+        &self,
+        text: &str,
+    ) -> Result<Detection, I18nError> {
+        let explanation = self.explain(text)?;
+        let method = match explanation.stage {
+            Stage::KeywordRegex | Stage::ScriptMatch => {
+                DetectionMethod::Pattern
+            }
+            Stage::WhatlangWhole | Stage::WhatlangWords => {
+                DetectionMethod::Whatlang
+            }
+        };
+
+        Ok(Detection {
+            code: explanation.language,
+            method,
+            range: explanation.matched.map(|matched| matched.range),
+        })
+    }
+
+    /// Detects the language together with warnings about its reliability.
+    ///
+    /// The warnings flag conditions under which detection is known to be
+    /// inaccurate, so callers can tell users that the guess may be wrong.
+    /// Japanese text mixing kana and Han ideographs is not considered to
+    /// mix scripts.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(String, Vec<DetectionWarning>), I18nError>` - The language code and the warnings that apply, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{DetectionWarning, LanguageDetector};
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let (lang, warnings) = detector.detect_with_warnings("Le chat et la souris").unwrap();
+    /// assert_eq!(lang, "fr");
+    /// assert!(warnings.is_empty());
+    ///
+    /// let (_, warnings) = detector.detect_with_warnings("Bonjour").unwrap();
+    /// assert_eq!(warnings, [DetectionWarning::TooShort]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as `detect`.
+    pub fn detect_with_warnings(
+        &self,
+        text: &str,
+    ) -> Result<(String, Vec<DetectionWarning>), I18nError> {
+        let normalized_text = self.prepare(text)?;
+        let (lang, stage) =
+            self.detect_prepared(&normalized_text, &[])?;
+        let mut warnings = Vec::new();
+
+        if text.split_whitespace().count() < MIN_RELIABLE_WORDS {
+            warnings.push(DetectionWarning::TooShort);
+        }
+        if has_mixed_scripts(text) {
+            warnings.push(DetectionWarning::MixedScripts);
+        }
+        let confidence = self.share_of(&normalized_text, &lang);
+        if Confidence::from_score(confidence) == Confidence::Low {
+            warnings.push(DetectionWarning::LowConfidence);
+        }
+        if stage == Stage::WhatlangWords {
+            warnings.push(DetectionWarning::WordByWordFallbackUsed);
+        }
+
+        Ok((lang, warnings))
+    }
+
+    /// Detects the language together with a coarse confidence level.
+    ///
+    /// This is `detect_with_confidence` with the numeric confidence mapped
+    /// through `Confidence::from_score`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(String, Confidence), I18nError>` - The language code and its confidence level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{Confidence, LanguageDetector};
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let (lang, level) = detector.detect_leveled("Der Hund und die Katze").unwrap();
+    /// assert_eq!(lang, "de");
+    /// assert_eq!(level, Confidence::High);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as `detect`.
+    pub fn detect_leveled(
+        &self,
+        text: &str,
+    ) -> Result<(String, Confidence), I18nError> {
+        let result = self.detect_with_confidence(text)?;
+        Ok((result.language, Confidence::from_score(result.confidence)))
+    }
+
+    /// Scores the keyword evidence for the most likely language.
+    ///
+    /// Each language pattern is matched against the whole text and the
+    /// number of distinct keywords (or script runs) it hits is counted.
+    /// The language with the most distinct hits wins, ties going to the
+    /// earlier pattern, and its confidence is its match density: distinct
+    /// hits per word, capped at `1.0`. A single coincidental "le" in a long
+    /// sentence therefore scores far lower than a sentence full of French
+    /// function words.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(String, f64), I18nError>` - The language code and its confidence between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let (lang, dense) = detector.keyword_confidence("le chat dans la maison").unwrap();
+    /// assert_eq!(lang, "fr");
+    /// let (_, sparse) = detector.keyword_confidence("le weekend starts tomorrow").unwrap();
+    /// assert!(dense > sparse);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed` if
+    /// the input is empty, non-alphabetic, or matches no pattern.
+    pub fn keyword_confidence(
+        &self,
+        text: &str,
+    ) -> Result<(String, f64), I18nError> {
+        let normalized_text = self.prepare(text)?;
+        let word_count = normalized_text.split_whitespace().count();
+        let (lang, hits) = self
+            .keyword_hits(&normalized_text)
+            .into_iter()
+            .filter(|(lang, _)| !strict_rejects(lang))
+            .fold(None, |best: Option<(&str, usize)>, (lang, hits)| {
+                match best {
+                    Some((_, most)) if most >= hits => best,
+                    _ => Some((lang, hits)),
+                }
+            })
+            .ok_or(I18nError::LanguageDetectionFailed)?;
+
+        let density = (hits as f64 / word_count.max(1) as f64).min(1.0);
+        Ok((lang.to_string(), density))
+    }
+
+    /// Counts the distinct keyword hits of every matching pattern, in
+    /// pattern order.
+    fn keyword_hits(&self, text: &str) -> Vec<(&'static str, usize)> {
+        self.patterns
+            .iter()
+            .filter_map(|(pattern, lang)| {
+                let distinct: HashSet<String> = pattern
+                    .find_iter(text)
+                    .map(|m| m.as_str().to_lowercase())
+                    .collect();
+                if distinct.is_empty() {
+                    None
+                } else {
+                    Some((*lang, distinct.len()))
+                }
+            })
+            .collect()
+    }
+
+    /// Attributes each word of the text to a language and returns the
+    /// per-language share of attributed words, highest first.
+    ///
+    /// A word matched by several language patterns (such as "la", shared
+    /// by French and Spanish) goes to the language with the most distinct
+    /// keyword hits across the whole text. Ties are broken by the order in
+    /// which languages first appear.
+    fn score_languages(
+        &self,
+        text: &str,
+    ) -> Result<Vec<(String, f64)>, I18nError> {
+        self.score_prepared(&self.prepare(text)?)
+    }
+
+    /// Returns the share of the words of text already cleaned by `prepare`
+    /// that support `language`, or `0.0` if none do.
+    fn share_of(&self, normalized_text: &str, language: &str) -> f64 {
+        self.score_prepared(normalized_text)
+            .ok()
+            .and_then(|scores| {
+                scores
+                    .into_iter()
+                    .find(|(lang, _)| lang == language)
+                    .map(|(_, score)| score)
+            })
+            .unwrap_or(0.0)
+    }
+
+    /// Scores text already cleaned by `prepare`, as `score_languages`.
+    fn score_prepared(
+        &self,
+        normalized_text: &str,
+    ) -> Result<Vec<(String, f64)>, I18nError> {
+        let word_count = normalized_text.split_whitespace().count();
+        let hits = self.keyword_hits(normalized_text);
+        let mut counts: Vec<(String, usize)> = Vec::new();
+
+        for word in normalized_text.split_whitespace() {
+            let word =
+                word.trim_matches(|c: char| !c.is_alphanumeric());
+            if !word.chars().any(char::is_alphabetic) {
+                continue;
+            }
+            let lang = match self.match_densest_pattern(word, &hits) {
+                Some(lang) => Some(lang.to_string()),
+                None => self.match_statistical(word, word_count, &[]),
+            };
+            if let Some(lang) = lang {
+                match counts.iter_mut().find(|(l, _)| *l == lang) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((lang, 1)),
+                }
+            }
+        }
+
+        let total: usize = counts.iter().map(|(_, count)| count).sum();
+        if total == 0 {
+            return Err(I18nError::LanguageDetectionFailed);
+        }
+
+        // `sort_by_key` is stable, so ties keep their order of appearance.
+        counts.sort_by_key(|(_, count)| Reverse(*count));
+        if let Some((top, _)) = counts.first() {
+            if counts.iter().all(|(lang, _)| strict_rejects(lang)) {
+                return Err(I18nError::UnsupportedLanguage(
+                    top.clone(),
+                ));
+            }
+        }
+        Ok(counts
+            .into_iter()
+            .filter(|(lang, _)| !strict_rejects(lang))
+            .map(|(lang, count)| (lang, count as f64 / total as f64))
+            .collect())
+    }
+
+    /// Returns the language of the pattern matching `word` with the most
+    /// distinct hits in the surrounding text, ties going to the earlier
+    /// pattern.
+    fn match_densest_pattern(
+        &self,
+        word: &str,
+        hits: &[(&'static str, usize)],
+    ) -> Option<&'static str> {
+        let mut best: Option<(&'static str, usize)> = None;
+        for (pattern, lang) in self.patterns.iter() {
+            if !pattern.is_match(word) {
+                continue;
+            }
+            let count = hits
+                .iter()
+                .find(|(l, _)| l == lang)
+                .map_or(0, |(_, count)| *count);
+            match best {
+                Some((_, most)) if most >= count => {}
+                _ => best = Some((lang, count)),
+            }
+        }
+        best.map(|(lang, _)| lang)
+    }
+
+    /// Detects the language using only the custom keyword and script
+    /// patterns, returning `None` when no pattern matches or the input is
+    /// not linguistic.
+    pub(crate) fn detect_pattern(&self, text: &str) -> Option<String> {
+        let normalized_text = self.prepare(text).ok()?;
+        self.match_patterns(&normalized_text, &[])
+            .map(str::to_string)
+    }
+
+    /// Normalises the input and rejects empty or non-alphabetic text.
+    fn prepare<'a>(
+        &self,
+        text: &'a str,
+    ) -> Result<Cow<'a, str>, I18nError> {
+        // Byte order marks and zero-width characters carry no signal
+        let normalized_text = match sanitize_input(text) {
+            Cow::Borrowed(text) => self.trim_input(text),
+            Cow::Owned(text) => {
+                Cow::Owned(self.trim_input(&text).into_owned())
+            }
+        };
+
+        // Reject empty or non-alphabetic input
+        if normalized_text.is_empty()
+            || !normalized_text.chars().any(|c| c.is_alphabetic())
+        {
+            error!("Empty or non-alphabetic input: {}", text);
+            return Err(I18nError::LanguageDetectionFailed);
+        }
+
+        Ok(normalized_text)
+    }
+
+    /// Truncates the text to the configured prefix, removes symbols when
+    /// neutral symbols are enabled, and trims surrounding whitespace.
+    fn trim_input<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let text = match self.max_prefix {
+            Some(max_chars) => grapheme_prefix(text, max_chars),
+            None => text,
+        };
+        if self.neutral_symbols {
+            match strip_symbols(text) {
+                Cow::Borrowed(t) => Cow::Borrowed(t.trim()),
+                Cow::Owned(t) => Cow::Owned(t.trim().to_string()),
+            }
+        } else {
+            Cow::Borrowed(text.trim())
+        }
+    }
+
+    /// Returns the language whose patterns have the most distinct hits in
+    /// the text, skipping patterns for excluded languages.
+    ///
+    /// Hits are tallied per language across all of its patterns, so a
+    /// stray English "the" in a French sentence is outvoted by the French
+    /// keywords around it. Ties go to the language whose pattern comes
+    /// first.
+    fn match_patterns(
+        &self,
+        text: &str,
+        excluded: &[&str],
+    ) -> Option<&'static str> {
+        let mut tally: Vec<(&'static str, usize)> = Vec::new();
+        for (lang, hits) in self.keyword_hits(text) {
+            if excluded.contains(&lang) {
+                continue;
+            }
+            match tally.iter_mut().find(|(l, _)| *l == lang) {
+                Some((_, count)) => *count += hits,
+                None => tally.push((lang, hits)),
+            }
+        }
+
+        let mut best: Option<(&'static str, usize)> = None;
+        for (lang, count) in tally {
+            match best {
+                Some((_, most)) if most >= count => {}
+                _ => best = Some((lang, count)),
+            }
+        }
+        best.map(|(lang, count)| {
+            debug!(
+                "Keyword patterns matched {} distinct hits for language '{}'",
+                count, lang
+            );
+            lang
+        })
+    }
+
+    /// Returns `true` if the text has a Latin-script word that is not
+    /// entirely matched by a keyword pattern.
+    fn has_content_word(&self, text: &str) -> bool {
+        text.split_whitespace()
+            .map(|word| {
+                word.trim_matches(|c: char| !c.is_alphanumeric())
+            })
+            .filter(|word| {
+                Script::dominant(word) == Some(Script::Latin)
+            })
+            .any(|word| {
+                !self.patterns.iter().any(|(pattern, _)| {
+                    pattern.find(word).map_or(false, |m| {
+                        m.start() == 0 && m.end() == word.len()
+                    })
+                })
+            })
+    }
+
+    /// Detects the language word-by-word using `whatlang`, returning the
+    /// first sufficiently confident result.
+    ///
+    /// `word_count` is the length of the whole text being detected, which
+    /// decides how much confidence is required. Results for excluded
+    /// languages are discarded.
+    fn match_statistical(
+        &self,
+        text: &str,
+        word_count: usize,
+        excluded: &[&str],
+    ) -> Option<String> {
+        for word in text.split_whitespace() {
+            if let Some(info) = detect(word) {
+                if self.is_confident(&info, word_count) {
+                    let lang = self.convert_lang_code(info.lang());
+                    if excluded.contains(&lang.as_str()) {
+                        continue;
+                    }
+                    debug!(
+                        "Detected language '{}' for word '{}'",
+                        lang, word
+                    );
+                    return Some(lang);
+                }
+            }
+        }
+        None
+    }
+
+    /// Detects the language of the whole text using `whatlang`, returning
+    /// `None` if the result is not confident enough or is excluded.
+    fn match_whole(
+        &self,
+        text: &str,
+        word_count: usize,
+        excluded: &[&str],
+    ) -> Option<String> {
+        let info = detect(text)?;
+        if !self.is_confident(&info, word_count) {
+            return None;
+        }
+        let lang = self.convert_lang_code(info.lang());
+        if excluded.contains(&lang.as_str()) {
+            None
+        } else {
+            Some(lang)
+        }
+    }
+
+    /// Returns the `whatlang` language when reconciliation is enabled and
+    /// it confidently contradicts a keyword match for `keyword_lang`.
+    fn reconcile(
+        &self,
+        text: &str,
+        keyword_lang: &str,
+        excluded: &[&str],
+    ) -> Option<String> {
+        if !self.reconcile {
+            return None;
+        }
+        let info = detect(text).filter(Info::is_reliable)?;
+        let statistical = self.convert_lang_code(info.lang());
+        if statistical == keyword_lang
+            || excluded.contains(&statistical.as_str())
+        {
+            return None;
+        }
+
+        let word_count = text.split_whitespace().count().max(1);
+        let hits = self
+            .keyword_hits(text)
+            .into_iter()
+            .find(|(lang, _)| *lang == keyword_lang)
+            .map_or(0, |(_, hits)| hits);
+        let keyword_share = (hits as f64 / word_count as f64).min(1.0);
+        if info.confidence() > keyword_share {
+            debug!(
+                "Statistical '{}' ({:.2}) overrides keyword '{}' ({:.2})",
+                statistical,
+                info.confidence(),
+                keyword_lang,
+                keyword_share
+            );
+            Some(statistical)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if a `whatlang` result is confident enough to accept
+    /// for a text of `word_count` words.
+    fn is_confident(&self, info: &Info, word_count: usize) -> bool {
+        if word_count < self.min_words_for_statistical {
+            info.is_reliable()
+                && info.confidence() >= SHORT_TEXT_CONFIDENCE
+        } else {
+            info.is_reliable()
+                || info.confidence() > self.confidence_threshold()
+        }
+    }
+
+    /// Splits the text into paragraphs and detects the language of each.
+    ///
+    /// Paragraphs are separated by blank lines and returned in reading
+    /// order with their byte ranges; paragraphs whose language cannot be
+    /// detected are left out. With `merge_adjacent`, consecutive segments
+    /// of the same language are coalesced into one span covering the blank
+    /// lines between them, giving the minimal set of language spans.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the document to analyze.
+    /// * `merge_adjacent` - Whether to merge consecutive same-language segments.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Segment>` - The language segments in order of appearance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let text = "The cat\n\nThe dog\n\nLe chat";
+    ///
+    /// let segments = detector.detect_segments(text, false);
+    /// assert_eq!(segments.len(), 3);
+    ///
+    /// let merged = detector.detect_segments(text, true);
+    /// assert_eq!(merged.len(), 2);
+    /// assert_eq!(&text[merged[0].range.clone()], "The cat\n\nThe dog");
+    /// assert_eq!(merged[1].language, "fr");
+    /// ```
+    pub fn detect_segments(
+        &self,
+        text: &str,
+        merge_adjacent: bool,
+    ) -> Vec<Segment> {
+        let mut segments: Vec<Segment> = Vec::new();
+
+        for range in paragraphs(text) {
+            let language = match self
+                .detect_excluding(&text[range.clone()], &[])
+            {
+                Ok(language) => language,
+                Err(_) => continue,
+            };
+            match segments.last_mut() {
+                Some(last)
+                    if merge_adjacent && last.language == language =>
+                {
+                    last.range.end = range.end;
+                }
+                _ => segments.push(Segment { range, language }),
+            }
+        }
+
+        segments
+    }
+
+    /// Detects the language, refusing to guess on weak evidence.
+    ///
+    /// Detection fails if the trimmed text is shorter than the length set
+    /// with `with_min_text_length`, or if `whatlang`'s confidence in its
+    /// own reading of the whole text is below `confidence_threshold`, even
+    /// when the keyword or script stages would have decided the language.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if the evidence is too weak.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new()
+    ///     .with_min_text_length(5)
+    ///     .with_confidence_threshold(0.5);
+    /// assert!(detector.detect_strict("Ok").is_err());
+    /// assert_eq!(
+    ///     detector
+    ///         .detect_strict("Der Hund schläft im Garten unter dem Baum")
+    ///         .unwrap(),
+    ///     "de"
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::LanguageDetectionFailed` if
+    /// the text is too short, `whatlang` is not confident enough, or the
+    /// language cannot be detected.
+    pub fn detect_strict(
+        &self,
+        text: &str,
+    ) -> Result<String, I18nError> {
+        let length = text.trim().chars().count();
+        if length < self.min_text_length {
+            debug!(
+                "Text of {} characters is below the minimum of {}",
+                length, self.min_text_length
+            );
+            return Err(I18nError::LanguageDetectionFailed);
+        }
+
+        let (lang, _) = self.detect_staged(text, &[])?;
+        let normalized_text = self.prepare(text)?;
+        match detect(&normalized_text) {
+            Some(info)
+                if info.confidence() >= self.confidence_threshold() =>
+            {
+                Ok(lang)
+            }
+            _ => {
+                debug!(
+                    "Confidence too low for strict detection: {}",
+                    text
+                );
+                Err(I18nError::LanguageDetectionFailed)
+            }
+        }
+    }
+
+    /// Detects the language of a short input such as a title, filename or
+    /// identifier.
+    ///
+    /// Filenames and identifiers are first split into words: a trailing
+    /// file extension is dropped, `_`, `-` and `.` separate words, as do
+    /// `camelCase` boundaries, and tokens containing digits are ignored.
+    /// The words are then matched by script and keyword patterns, and
+    /// `whatlang` is only trusted when it reports a reliable result with a
+    /// confidence of at least 0.9. Weak signals fail rather than guess.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the short text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if the signal is too weak.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// assert_eq!(detector.detect_short("le_rapport-2023.pdf").unwrap(), "fr");
+    /// assert_eq!(detector.detect_short("derBericht.txt").unwrap(), "de");
+    /// assert!(detector.detect_short("x7_final.doc").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::LanguageDetectionFailed` if
+    /// no script, keyword or high-confidence statistical signal is found.
+    pub fn detect_short(
+        &self,
+        text: &str,
+    ) -> Result<String, I18nError> {
+        let words = identifier_words(text);
+        let normalized_text = self.prepare(&words)?;
+
+        if let Some(lang) = script_language(&normalized_text) {
+            return check_detected(lang.to_string());
+        }
+        if let Some(lang) = self.match_patterns(&normalized_text, &[]) {
+            return check_detected(lang.to_string());
+        }
+        match detect(&normalized_text) {
+            Some(info)
+                if info.is_reliable()
+                    && info.confidence() >= SHORT_TEXT_CONFIDENCE =>
+            {
+                check_detected(self.convert_lang_code(info.lang()))
+            }
+            _ => {
+                debug!("Signal too weak for short text: {}", text);
+                Err(I18nError::LanguageDetectionFailed)
+            }
+        }
+    }
+
+    /// Detects the language of a document read incrementally.
+    ///
+    /// The input is read in 4 KiB chunks and detection is attempted after
+    /// each one, stopping as soon as the language is identified with high
+    /// confidence. At most the first 64 KiB are read, so large documents
+    /// are never loaded into memory as a whole.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The buffered reader to read UTF-8 text from.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use std::io::Cursor;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let document = "Le chat dort sur le canapé.\n".repeat(10_000);
+    /// let mut reader = Cursor::new(document.as_bytes());
+    /// assert_eq!(detector.detect_reader(&mut reader).unwrap(), "fr");
+    /// assert!(reader.position() < document.len() as u64);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::UnexpectedError` if reading
+    /// fails or the input is not valid UTF-8, and the same errors as
+    /// `detect` otherwise.
+    pub fn detect_reader<R: BufRead>(
+        &self,
+        mut reader: R,
+    ) -> Result<String, I18nError> {
+        let mut bytes = Vec::new();
+        loop {
+            let read = reader
+                .by_ref()
+                .take(STREAM_CHUNK_BYTES)
+                .read_to_end(&mut bytes)
+                .map_err(|e| {
+                    I18nError::UnexpectedError(format!(
+                        "Failed to read text: {}",
+                        e
+                    ))
+                })?;
+            if read == 0 || bytes.len() >= STREAM_MAX_BYTES {
+                break;
+            }
+            if let Ok(result) =
+                self.detect_with_confidence(utf8_prefix(&bytes)?)
+            {
+                if Confidence::from_score(result.confidence)
+                    == Confidence::High
+                {
+                    debug!(
+                        "Detected '{}' after {} bytes",
+                        result.language,
+                        bytes.len()
+                    );
+                    return Ok(result.language);
+                }
+            }
+        }
+
+        self.detect_staged(utf8_prefix(&bytes)?, &[])
+            .map(|(lang, _)| lang)
+    }
+
+    /// Returns up to `n` candidate languages, most likely first.
+    ///
+    /// Every word of the text supports the languages whose keyword pattern
+    /// matches it, or failing that the language `whatlang` confidently
+    /// assigns to it. A candidate's confidence is the share of words
+    /// supporting it, so a language matching many words ranks higher.
+    /// Ties keep the order in which the languages first appear.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    /// * `n` - The maximum number of candidates to return.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<(String, f64)>, I18nError>` - The candidates with their confidence, in descending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let candidates = detector.detect_candidates("Hello bonjour hallo", 3).unwrap();
+    /// let languages: Vec<&str> = candidates.iter().map(|(l, _)| l.as_str()).collect();
+    /// assert_eq!(languages, ["en", "fr", "de"]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::LanguageDetectionFailed` if
+    /// the text is empty or non-alphabetic, or no word supports any
+    /// language.
+    pub fn detect_candidates(
+        &self,
+        text: &str,
+        n: usize,
+    ) -> Result<Vec<(String, f64)>, I18nError> {
+        let normalized_text = self.prepare(text)?;
+        let word_count = normalized_text.split_whitespace().count();
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        let mut support = |lang: String| match counts
+            .iter_mut()
+            .find(|(l, _)| *l == lang)
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((lang, 1)),
+        };
+
+        for word in normalized_text.split_whitespace() {
+            let mut matched = false;
+            for (pattern, lang) in self.patterns.iter() {
+                if pattern.is_match(word) {
+                    matched = true;
+                    support(lang.to_string());
+                }
+            }
+            if matched {
+                continue;
+            }
+            if let Some(info) = detect(word) {
+                if self.is_confident(&info, word_count) {
+                    support(self.convert_lang_code(info.lang()));
+                }
+            }
+        }
+
+        if counts.is_empty() {
+            return Err(I18nError::LanguageDetectionFailed);
+        }
+        counts.sort_by_key(|(_, count)| Reverse(*count));
+        Ok(counts
+            .into_iter()
+            .filter(|(lang, _)| !strict_rejects(lang))
+            .take(n)
+            .map(|(lang, count)| {
+                (lang, count as f64 / word_count as f64)
+            })
+            .collect())
+    }
+
+    /// Returns the detection evidence as a probability distribution over
+    /// languages.
+    ///
+    /// Three sources of evidence are added up per language: a weight of 1
+    /// for a language implied by the script, each language's share of
+    /// keyword-matched words, and the `whatlang` confidence for its pick on
+    /// the whole text. The totals are then normalised to sum to 1.0.
+    /// Languages without any evidence are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HashMap<String, f64>, I18nError>` - Each language with evidence mapped to its probability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let distribution = detector.language_distribution("Le chat et le chien").unwrap();
+    /// let total: f64 = distribution.values().sum();
+    /// assert!((total - 1.0).abs() < 1e-9);
+    /// assert!(distribution["fr"] > 0.5);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::LanguageDetectionFailed` if
+    /// the text is empty or non-alphabetic, or no source yields evidence.
+    pub fn language_distribution(
+        &self,
+        text: &str,
+    ) -> Result<HashMap<String, f64>, I18nError> {
+        let normalized_text = self.prepare(text)?;
+        let mut evidence: HashMap<String, f64> = HashMap::new();
+
+        if let Some(lang) = script_language(&normalized_text) {
+            *evidence.entry(lang.to_string()).or_insert(0.0) += 1.0;
+        }
+        for (lang, share) in self.score_languages(text)? {
+            *evidence.entry(lang).or_insert(0.0) += share;
+        }
+        if let Some(info) = detect(&normalized_text) {
+            *evidence
+                .entry(self.convert_lang_code(info.lang()))
+                .or_insert(0.0) += info.confidence();
+        }
+
+        evidence.retain(|lang, weight| {
+            *weight > 0.0 && !strict_rejects(lang)
+        });
+        let total: f64 = evidence.values().sum();
+        if total <= 0.0 {
+            return Err(I18nError::LanguageDetectionFailed);
+        }
+        for weight in evidence.values_mut() {
+            *weight /= total;
+        }
+        Ok(evidence)
+    }
+
+    /// Detects the language of each text in a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Result<String, I18nError>>` - One detection result per text, in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let results = detector.detect_batch(&["The cat", "Le chat", "42"]);
+    /// assert_eq!(results[0].as_deref(), Ok("en"));
+    /// assert_eq!(results[1].as_deref(), Ok("fr"));
+    /// assert!(results[2].is_err());
+    /// ```
+    pub fn detect_batch(
+        &self,
+        texts: &[&str],
+    ) -> Vec<Result<String, I18nError>> {
+        texts
+            .iter()
+            .map(|text| self.detect_excluding(text, &[]))
+            .collect()
+    }
+
+    /// Asynchronously detects the language of each text in a batch.
+    ///
+    /// The batch is split into at most four chunks, each detected on the
+    /// blocking thread pool, so large batches do not flood the pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Result<String, I18nError>>` - One detection result per text, in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let detector = LanguageDetector::new();
+    ///     let results = detector.detect_batch_async(&["The cat", "Der Hund"]).await;
+    ///     assert_eq!(results[1].as_deref(), Ok("de"));
+    /// }
+    /// ```
+    pub async fn detect_batch_async(
+        &self,
+        texts: &[&str],
+    ) -> Vec<Result<String, I18nError>> {
+        if texts.is_empty() {
+            return Vec::new();
+        }
+
+        let chunk_size =
+            (texts.len() + MAX_BATCH_TASKS - 1) / MAX_BATCH_TASKS;
+        let handles: Vec<_> = texts
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let chunk: Vec<String> =
+                    chunk.iter().map(|text| text.to_string()).collect();
+                let detector = self.clone();
+                let len = chunk.len();
+                let handle = task::spawn_blocking(move || {
+                    chunk
+                        .iter()
+                        .map(|text| {
+                            detector.detect_excluding(text, &[])
+                        })
+                        .collect::<Vec<_>>()
+                });
+                (len, handle)
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(texts.len());
+        for (len, handle) in handles {
+            match handle.await {
+                Ok(chunk) => results.extend(chunk),
+                Err(e) => {
+                    error!("Batch detection task failed: {:?}", e);
+                    results.extend((0..len).map(|_| {
+                        Err(I18nError::LanguageDetectionFailed)
+                    }));
+                }
+            }
+        }
+        results
+    }
+
+    /// Detects the common language of a batch of texts.
+    ///
+    /// Each text is detected on its own. Texts whose language cannot be
+    /// detected, typically very short ones, are ignored as long as they
+    /// make up no more than a quarter of the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - The texts to check.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The language shared by every detectable text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::error::I18nError;
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let batch = ["The cat is black", "The dog is white", "A bird", "42"];
+    /// assert_eq!(detector.detect_consistent(&batch).unwrap(), "en");
+    ///
+    /// let mixed = ["The cat is black", "Le chat est noir"];
+    /// assert_eq!(
+    ///     detector.detect_consistent(&mixed),
+    ///     Err(I18nError::InconsistentLanguages(vec![
+    ///         "en".to_string(),
+    ///         "fr".to_string()
+    ///     ]))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return:
+    /// - `I18nError::InconsistentLanguages` listing the distinct languages
+    ///   found if the texts disagree.
+    /// - `I18nError::LanguageDetectionFailed` if the batch is empty or too
+    ///   many texts are undetectable.
+    pub fn detect_consistent(
+        &self,
+        texts: &[&str],
+    ) -> Result<String, I18nError> {
+        let mut languages: Vec<String> = Vec::new();
+        let mut undetectable = 0;
+
+        for text in texts {
+            match self.detect_excluding(text, &[]) {
+                Ok(language) => {
+                    if !languages.contains(&language) {
+                        languages.push(language);
+                    }
+                }
+                Err(_) => undetectable += 1,
+            }
+        }
+
+        if undetectable as f64
+            > texts.len() as f64 * MAX_UNDETECTABLE_SHARE
+        {
+            debug!("Too many undetectable texts: {}", undetectable);
+            return Err(I18nError::LanguageDetectionFailed);
+        }
+        match languages.len() {
+            0 => Err(I18nError::LanguageDetectionFailed),
+            1 => Ok(languages.remove(0)),
+            _ => Err(I18nError::InconsistentLanguages(languages)),
+        }
+    }
+
+    /// Measures detection accuracy against a labeled data set.
+    ///
+    /// Each text is detected and compared to its label, producing overall
+    /// accuracy, per-language precision and recall, and a confusion matrix
+    /// of true to predicted language counts. Texts that cannot be detected
+    /// are counted as predicting `und`.
+    ///
+    /// # Arguments
+    ///
+    /// * `labeled` - `(text, true language)` pairs.
+    ///
+    /// # Returns
+    ///
+    /// * `EvalReport` - The accuracy metrics and confusion matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// let report = detector.evaluate(&[
+    ///     ("The cat", "en"),
+    ///     ("Le chat", "fr"),
+    ///     ("Der Hund", "de"),
+    ///     ("The dog", "fr"),
+    /// ]);
+    /// assert_eq!(report.accuracy, 0.75);
+    /// assert_eq!(report.per_language["fr"].recall, 0.5);
+    /// assert_eq!(report.confusion[&("fr".to_string(), "en".to_string())], 1);
+    /// ```
+    pub fn evaluate(&self, labeled: &[(&str, &str)]) -> EvalReport {
+        let mut report = EvalReport::default();
+        if labeled.is_empty() {
+            return report;
+        }
+
+        let mut correct = 0;
+        for (text, truth) in labeled {
+            let predicted = self
+                .detect_excluding(text, &[])
+                .unwrap_or_else(|_| "und".to_string());
+            if predicted == *truth {
+                correct += 1;
+            }
+            *report
+                .confusion
+                .entry((truth.to_string(), predicted))
+                .or_insert(0) += 1;
+        }
+        report.accuracy = correct as f64 / labeled.len() as f64;
+
+        let mut languages: HashSet<&str> = HashSet::new();
+        for (truth, predicted) in report.confusion.keys() {
+            let _ = languages.insert(truth);
+            let _ = languages.insert(predicted);
+        }
+        for language in languages {
+            let (mut hits, mut labeled_as, mut predicted_as) =
+                (0, 0, 0);
+            for ((truth, predicted), &count) in &report.confusion {
+                if truth == language {
+                    labeled_as += count;
+                }
+                if predicted == language {
+                    predicted_as += count;
+                }
+                if truth == language && predicted == language {
+                    hits += count;
+                }
+            }
+            let ratio = |total: usize| {
+                if total == 0 {
+                    0.0
+                } else {
+                    hits as f64 / total as f64
+                }
+            };
+            let metrics = LanguageMetrics {
+                precision: ratio(predicted_as),
+                recall: ratio(labeled_as),
+            };
+            let _ = report
+                .per_language
+                .insert(language.to_string(), metrics);
+        }
+        report
+    }
+
+    /// Distinguishes Simplified from Traditional Chinese.
+    ///
+    /// Characters that exist in only one of the two variants (such as
+    /// `这` versus `這`) are counted, and the variant with more of them
+    /// wins. Text using only characters shared by both variants, or an
+    /// equal number of each, is reported as ambiguous.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<ChineseVariant>` - The variant, or `None` if the text contains no Han characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{ChineseVariant, LanguageDetector};
+    ///
+    /// let detector = LanguageDetector::new();
+    /// assert_eq!(detector.detect_chinese_variant("这是中国"), Some(ChineseVariant::Simplified));
+    /// assert_eq!(detector.detect_chinese_variant("這是中國"), Some(ChineseVariant::Traditional));
+    /// assert_eq!(detector.detect_chinese_variant("中文"), Some(ChineseVariant::Ambiguous));
+    /// assert_eq!(detector.detect_chinese_variant("Hello"), None);
+    /// ```
+    pub fn detect_chinese_variant(
+        &self,
+        text: &str,
+    ) -> Option<ChineseVariant> {
+        if !text.chars().any(|c| Script::of(c) == Some(Script::Han)) {
+            return None;
+        }
+
+        let (mut simplified, mut traditional) = (0, 0);
+        for c in text.chars() {
+            for (s, t) in CHINESE_VARIANT_PAIRS {
+                if c == *s {
+                    simplified += 1;
+                } else if c == *t {
+                    traditional += 1;
+                }
+            }
+        }
+
+        Some(if simplified > traditional {
+            ChineseVariant::Simplified
+        } else if traditional > simplified {
+            ChineseVariant::Traditional
+        } else {
+            ChineseVariant::Ambiguous
+        })
+    }
+
+    /// Runs a closure with a detector owned by the current thread.
+    ///
+    /// The detector is constructed lazily on first use in each thread and
+    /// reused afterwards, so repeated calls neither allocate a new detector
+    /// nor contend on a shared lock.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure to run with the thread's detector.
+    ///
+    /// # Returns
+    ///
+    /// * `R` - The closure's result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let lang = LanguageDetector::with_thread_local(|d| d.detect("Hallo Welt"));
+    /// assert_eq!(lang.unwrap(), "de");
+    /// ```
+    pub fn with_thread_local<R>(
+        f: impl FnOnce(&LanguageDetector) -> R,
+    ) -> R {
+        THREAD_DETECTOR.with(f)
+    }
+
+    /// Returns the scripts covered by the built-in detection patterns.
+    ///
+    /// Text written in other scripts can still be detected through the
+    /// `whatlang` fallback, but without dedicated heuristics.
+    ///
+    /// # Returns
+    ///
+    /// * `&'static [Script]` - The recognised scripts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::script::Script;
+    ///
+    /// let scripts = LanguageDetector::supported_scripts();
+    /// assert!(scripts.contains(&Script::Hangul));
+    /// ```
+    pub fn supported_scripts() -> &'static [Script] {
+        SUPPORTED_SCRIPTS
+    }
+
+    /// Detects the language and returns its code in the given format.
+    ///
+    /// Languages outside the built-in table keep the ISO 639-3 code
+    /// reported by `whatlang` in every format, which is also valid BCP 47.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    /// * `format` - The language code format to return.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code in the requested format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::{LangCodeFormat, LanguageDetector};
+    ///
+    /// let detector = LanguageDetector::new();
+    /// assert_eq!(detector.detect_as("Hello world", LangCodeFormat::Iso6391).unwrap(), "en");
+    /// assert_eq!(detector.detect_as("Hello world", LangCodeFormat::Iso6393).unwrap(), "eng");
+    /// assert_eq!(detector.detect_as("Hello world", LangCodeFormat::Bcp47).unwrap(), "en-US");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same errors as `detect`.
+    pub fn detect_as(
+        &self,
+        text: &str,
+        format: LangCodeFormat,
+    ) -> Result<String, I18nError> {
+        let code = self.detect_excluding(text, &[])?;
+        let entry =
+            LANG_CODES.iter().find(|(iso1, _, _)| *iso1 == code);
+        Ok(match (entry, format) {
+            (Some((_, iso3, _)), LangCodeFormat::Iso6393) => iso3,
+            (Some((_, _, bcp47)), LangCodeFormat::Bcp47) => bcp47,
+            _ => code.as_str(),
+        }
+        .to_string())
+    }
+
+    /// Converts `whatlang`'s language codes to the desired format.
+    ///
+    /// This function maps `whatlang`'s internal `Lang` enum values to their ISO 639-1
+    /// equivalents or other common language codes used by the application.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The `Lang` enum from `whatlang`.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The standardized language code (e.g., "en", "fr").
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use whatlang::Lang;
+    ///
+    /// let detector = LanguageDetector::new();
+    /// assert_eq!(detector.convert_lang_code(Lang::Eng), "en");
+    /// assert_eq!(detector.convert_lang_code(Lang::Fra), "fr");
+    /// assert_eq!(detector.convert_lang_code(Lang::Deu), "de");
+    /// ```
+    pub fn convert_lang_code(&self, lang: Lang) -> String {
+        match lang {
+            Lang::Eng => "en",
+            Lang::Fra => "fr",
+            Lang::Deu => "de",
+            Lang::Spa => "es",
+            Lang::Por => "pt",
+            Lang::Jpn => "ja",
+            Lang::Cmn => "zh",
+            Lang::Ara => "ar",
+            Lang::Hin => "hi",
+            Lang::Kor => "ko",
+            Lang::Rus => "ru",
+            Lang::Ita => "it",
+            Lang::Nld => "nl",
+            Lang::Heb => "he",
+            Lang::Ind => "id",
+            _ => lang.code(),
+        }
+        .to_string()
+    }
+}
+
+#[async_trait]
+impl LanguageDetectorTrait for LanguageDetector {
+    /// Detects the language of the given text synchronously.
+    ///
+    /// This method first attempts to detect the language using custom regular expression patterns
+    /// for common words. If no match is found, it falls back to `whatlang` for statistical detection.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let detector = LanguageDetector::new();
+    ///
+    /// // Detect English
+    /// let result = detector.detect("The quick brown fox");
+    /// assert_eq!(result.unwrap(), "en");
+    ///
+    /// // Detect French
+    /// let result = detector.detect("Le chat noir");
+    /// assert_eq!(result.unwrap(), "fr");
+    ///
+    /// // Detect German
+    /// let result = detector.detect("Der schnelle braune Fuchs");
+    /// assert_eq!(result.unwrap(), "de");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed` if:
+    /// - The input text is empty or contains only non-alphabetic characters.
+    /// - The language detection process fails to identify a language with sufficient confidence.
+    fn detect(&self, text: &str) -> Result<String, I18nError> {
+        self.detect_excluding(text, &[])
+    }
+
+    /// Detects the language of the given text asynchronously.
+    ///
+    /// This method provides the same functionality as `detect`, but operates asynchronously,
+    /// allowing for non-blocking language detection in concurrent contexts.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let detector = LanguageDetector::new();
+    ///
+    ///     // Detect Spanish asynchronously
+    ///     let result = detector.detect_async("El gato negro").await;
+    ///     assert_eq!(result.unwrap(), "es");
+    ///
+    ///     // Detect Portuguese asynchronously
+    ///     let result = detector.detect_async("O gato preto").await;
+    ///     assert_eq!(result.unwrap(), "pt");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed` if:
+    /// - The input text is empty or contains only non-alphabetic characters.
+    /// - The language detection process fails to identify a language with sufficient confidence.
+    async fn detect_async(
+        &self,
+        text: &str,
+    ) -> Result<String, I18nError> {
+        let text = text.to_string();
+        let detector = self.clone();
+
+        task::spawn_blocking(move || detector.detect(&text))
+            .await
+            .map_err(|e| {
+                error!("Async language detection task failed: {:?}", e);
+                I18nError::LanguageDetectionFailed
+            })?
+    }
+}
+
+/// Returns the most likely language for text written in `script`.
+///
+/// Scripts shared by several supported languages, such as Latin or Han,
+/// have no single default and return `None`.
+///
+/// # Arguments
+///
+/// * `script` - The script to look up.
+///
+/// # Returns
+///
+/// * `Option<&'static str>` - The language code, if the script implies one.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::language_detector::default_language_for_script;
+/// use langweave::script::Script;
+///
+/// assert_eq!(default_language_for_script(Script::Hangul), Some("ko"));
+/// assert_eq!(default_language_for_script(Script::Latin), None);
+/// ```
+pub fn default_language_for_script(
+    script: Script,
+) -> Option<&'static str> {
+    match script {
+        Script::Cyrillic => Some("ru"),
+        Script::Arabic => Some("ar"),
+        Script::Hebrew => Some("he"),
+        Script::Devanagari => Some("hi"),
+        Script::Hangul => Some("ko"),
+        Script::Hiragana | Script::Katakana => Some("ja"),
+        _ => None,
+    }
+}
+
+/// The share of script characters a single non-Latin script must reach for
+/// the script pre-check to decide the language.
+const DOMINANT_SCRIPT_SHARE: f64 = 0.9;
+
+/// Returns the language implied by the script of `text` when it is
+/// overwhelmingly written in one non-Latin script.
+///
+/// Japanese mixes kana with Han ideographs, so kana and Han together count
+/// as Japanese as soon as any kana is present.
+fn script_language(text: &str) -> Option<&'static str> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    for script in text.chars().filter_map(Script::of) {
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    let count = |script: Script| {
+        counts
+            .iter()
+            .find(|(s, _)| *s == script)
+            .map_or(0, |(_, count)| *count)
+    };
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+    let dominant =
+        |n: usize| n as f64 >= total as f64 * DOMINANT_SCRIPT_SHARE;
+
+    let kana = count(Script::Hiragana) + count(Script::Katakana);
+    if kana > 0 && dominant(kana + count(Script::Han)) {
+        return Some("ja");
+    }
+
+    let (script, n) = counts.into_iter().max_by_key(|(_, n)| *n)?;
+    if !dominant(n) {
+        return None;
+    }
+    match script {
+        Script::Han => Some("zh"),
+        _ => default_language_for_script(script),
+    }
+}
+
+/// Compiles the built-in patterns, in order.
+fn builtin_patterns() -> Result<Vec<(Regex, &'static str)>, regex::Error>
+{
+    PATTERN_SOURCES
+        .iter()
+        .map(|(source, lang)| Ok((compile_pattern(source)?, *lang)))
+        .collect()
+}
+
+/// Compiles a built-in pattern with the default size limits.
+fn compile_pattern(source: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(source)
+        .size_limit(DEFAULT_PATTERN_SIZE_LIMIT)
+        .dfa_size_limit(DEFAULT_PATTERN_DFA_SIZE_LIMIT)
+        .build()
+}
+
+/// Returns `true` if the text uses more than one script, counting the
+/// Japanese combination of kana and Han as a single script.
+fn has_mixed_scripts(text: &str) -> bool {
+    let mut seen: Option<Script> = None;
+    for script in text.chars().filter_map(Script::of) {
+        let script = match script {
+            Script::Hiragana | Script::Katakana => Script::Han,
+            other => other,
+        };
+        match seen {
+            Some(first) if first != script => return true,
+            _ => seen = Some(script),
+        }
+    }
+    false
+}
+
+/// Returns the byte ranges of the blank-line separated paragraphs of
+/// `text`, without trailing whitespace.
+fn paragraphs(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut current: Option<Range<usize>> = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end();
+        if content.trim_start().is_empty() {
+            ranges.extend(current.take());
+        } else {
+            let end = offset + content.len();
+            match current.as_mut() {
+                Some(range) => range.end = end,
+                None => current = Some(offset..end),
+            }
+        }
+        offset += line.len();
+    }
+
+    ranges.extend(current);
+    ranges
+}
+
+/// Returns `true` if the character is an emoji, pictograph, dingbat or an
+/// emoji modifier that carries no language signal.
+fn is_symbol(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200D}'
+            | '\u{20E3}'
+            | '\u{2190}'..='\u{2BFF}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{1F000}'..='\u{1FAFF}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// Splits a filename or identifier into space-separated words, dropping a
+/// trailing file extension and tokens that contain digits.
+fn identifier_words(text: &str) -> String {
+    let stem = match text.rfind('.') {
+        Some(dot)
+            if dot > 0
+                && (1..=4).contains(&(text.len() - dot - 1))
+                && text[dot + 1..]
+                    .chars()
+                    .all(char::is_alphanumeric) =>
+        {
+            &text[..dot]
+        }
+        _ => text,
+    };
+
+    let mut spaced = String::with_capacity(stem.len());
+    let mut previous_lowercase = false;
+    for c in stem.chars() {
+        if matches!(c, '_' | '-' | '.') {
+            spaced.push(' ');
+        } else {
+            // An uppercase letter after a lowercase one starts a new word
+            if c.is_uppercase() && previous_lowercase {
+                spaced.push(' ');
+            }
+            spaced.push(c);
+        }
+        previous_lowercase = c.is_lowercase();
+    }
+
+    spaced
+        .split_whitespace()
+        .filter(|word| !word.chars().any(|c| c.is_numeric()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Decodes the bytes as UTF-8, ignoring a character cut off at the end.
+fn utf8_prefix(bytes: &[u8]) -> Result<&str, I18nError> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        Err(e) if e.error_len().is_none() => {
+            Ok(std::str::from_utf8(&bytes[..e.valid_up_to()])
+                .unwrap_or_default())
+        }
+        Err(e) => Err(I18nError::UnexpectedError(format!(
+            "Invalid UTF-8 text: {}",
+            e
+        ))),
+    }
+}
+
+/// Returns the first `max_chars` characters of the text, extended to the
+/// end of the extended grapheme cluster the cut falls in.
+fn grapheme_prefix(text: &str, max_chars: usize) -> &str {
+    let mut count = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        if count >= max_chars {
+            return &text[..index];
+        }
+        count += grapheme.chars().count();
+    }
+    text
+}
+
+/// Maps a byte range of text produced by `prepare` back onto the input it
+/// was prepared from.
+///
+/// Preparation only removes characters, and never keeps a character
+/// equal to one it removed before it, so each prepared character is the
+/// next equal character of the input.
+fn input_range(
+    text: &str,
+    prepared: &str,
+    range: Range<usize>,
+) -> Range<usize> {
+    let mut input = text.char_indices();
+    let mut start = text.len();
+    let mut end = text.len();
+    for (offset, c) in prepared.char_indices() {
+        if offset >= range.end {
+            break;
+        }
+        if let Some((index, _)) =
+            input.find(|(_, original)| *original == c)
+        {
+            if offset == range.start {
+                start = index;
+            }
+            end = index + c.len_utf8();
+        }
+    }
+    start.min(end)..end
+}
+
+/// Removes emoji and symbol characters from the text, borrowing when the
+/// text contains none.
+fn strip_symbols(text: &str) -> Cow<'_, str> {
+    if text.chars().any(is_symbol) {
+        Cow::Owned(text.chars().filter(|c| !is_symbol(*c)).collect())
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Nonspacing marks (Unicode category `Mn`), such as combining accents.
+static NONSPACING_MARKS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\p{Mn}+")
+        .expect("Failed to compile nonspacing mark regex")
+});
+
+/// Removes diacritics by decomposing the text (NFD), stripping nonspacing
+/// marks and recomposing what remains (NFC), borrowing when the text is
+/// ASCII.
+fn fold_diacritics(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let decomposed: String = text.nfd().collect();
+    Cow::Owned(
+        NONSPACING_MARKS
+            .replace_all(&decomposed, "")
+            .nfc()
+            .collect(),
+    )
+}
+
+impl Default for LanguageDetector {
+    /// Provides a default instance of `LanguageDetector`.
+    ///
+    /// # Returns
+    ///
+    /// * `LanguageDetector` - A default instance using predefined patterns for common languages.
+    ///
+    /// This method allows the `LanguageDetector` to be initialized easily with default patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    ///
+    /// let detector = LanguageDetector::default();
+    /// let result = detector.detect("Hello, world!");
+    /// assert_eq!(result.unwrap(), "en");
+    /// ```
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A stateful detector that keeps a running best guess of the language of
+/// a stream of words.
+///
+/// Each pushed word adds evidence for a language: a keyword or script
+/// pattern match counts fully, and otherwise the `whatlang` confidence for
+/// the word is added. The leading language and its share of the evidence
+/// collected so far can be read at any time.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::language_detector::IncrementalDetector;
+///
+/// let mut detector = IncrementalDetector::new();
+/// assert!(detector.current_best().is_none());
+///
+/// for word in "der Hund und die Katze".split_whitespace() {
+///     detector.push_word(word);
+/// }
+/// let (lang, share) = detector.current_best().unwrap();
+/// assert_eq!(lang, "de");
+/// assert!(share > 0.5);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalDetector {
+    detector: LanguageDetector,
+    evidence: Vec<(String, f64)>,
+}
+
+impl IncrementalDetector {
+    /// Creates a new `IncrementalDetector` with no evidence.
+    ///
+    /// # Returns
+    ///
+    /// * `IncrementalDetector` - A detector using the default patterns.
+    #[must_use]
+    pub fn new() -> Self {
+        IncrementalDetector::default()
+    }
+
+    /// Adds the evidence of one word.
+    ///
+    /// Words without letters, or that no pattern or `whatlang` result can
+    /// be attributed to, are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - A string slice that holds the next word of the stream.
+    pub fn push_word(&mut self, word: &str) {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if !word.chars().any(char::is_alphabetic) {
+            return;
+        }
+
+        let (lang, weight) =
+            match self.detector.match_patterns(word, &[]) {
+                Some(lang) => (lang.to_string(), 1.0),
+                None => match detect(word) {
+                    Some(info) => (
+                        self.detector.convert_lang_code(info.lang()),
+                        info.confidence(),
+                    ),
+                    None => return,
+                },
+            };
+
+        match self.evidence.iter_mut().find(|(l, _)| *l == lang) {
+            Some((_, total)) => *total += weight,
+            None => self.evidence.push((lang, weight)),
+        }
+    }
+
+    /// Returns the leading language so far with its share of the evidence.
+    ///
+    /// Ties go to the language seen first.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(String, f64)>` - The language code and its share between `0.0` and `1.0`, or `None` before any evidence.
+    pub fn current_best(&self) -> Option<(String, f64)> {
+        let total: f64 = self.evidence.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        self.evidence
+            .iter()
+            .filter(|(lang, _)| !strict_rejects(lang))
+            .fold(
+                None,
+                |best: Option<&(String, f64)>, entry| match best {
+                    Some(b) if b.1 >= entry.1 => best,
+                    _ => Some(entry),
+                },
+            )
+            .map(|(lang, weight)| (lang.clone(), weight / total))
+    }
+}
+
+/// A detector that relies on `whatlang` statistical detection alone.
+///
+/// Unlike `LanguageDetector`, the built-in keyword and script patterns are
+/// never consulted, which helps measure their contribution and avoids
+/// their false positives. Language codes are mapped the same way as by
+/// `LanguageDetector::convert_lang_code`.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::language_detector::WhatlangDetector;
+/// use langweave::language_detector_trait::LanguageDetectorTrait;
+///
+/// let detector = WhatlangDetector::new();
+/// let text = "Le renard brun rapide saute par-dessus le chien paresseux";
+/// assert_eq!(detector.detect(text).unwrap(), "fr");
+/// assert!(detector.detect("12345").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct WhatlangDetector {
+    detector: LanguageDetector,
+}
+
+impl WhatlangDetector {
+    /// Creates a new `WhatlangDetector`.
+    ///
+    /// # Returns
+    ///
+    /// * `WhatlangDetector` - A detector running only the `whatlang` stages.
+    #[must_use]
+    pub fn new() -> Self {
+        WhatlangDetector {
+            detector: LanguageDetector::new().with_pipeline(&[
+                Stage::WhatlangWhole,
+                Stage::WhatlangWords,
+            ]),
+        }
+    }
+}
+
+impl Default for WhatlangDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LanguageDetectorTrait for WhatlangDetector {
+    /// Detects the language of the given text with `whatlang` only.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed`
+    /// if `whatlang` cannot identify the language with sufficient
+    /// confidence.
+    fn detect(&self, text: &str) -> Result<String, I18nError> {
+        self.detector.detect(text)
+    }
+
+    /// Detects the language of the given text asynchronously with
+    /// `whatlang` only.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed`
+    /// if `whatlang` cannot identify the language with sufficient
+    /// confidence.
+    async fn detect_async(
+        &self,
+        text: &str,
+    ) -> Result<String, I18nError> {
+        self.detector.detect_async(text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    #[test]
     fn test_language_detection() {
         let detector = LanguageDetector::new();
-        let test_cases = vec![
-            ("The quick brown fox", "en"),
-            ("Le chat noir", "fr"),
-            ("Der schnelle Fuchs", "de"),
-            ("El gato rápido", "es"),
-            ("O gato rápido", "pt"),
-            ("こんにちは", "ja"),
-            ("你好", "zh"),
-            ("مرحبا", "ar"),
-            ("नमस्ते", "hi"),
-            ("안녕하세요", "ko"),
-            ("Здравствуйте", "ru"),
-        ];
+        let test_cases = vec![
+            ("The quick brown fox", "en"),
+            ("Le chat noir", "fr"),
+            ("Der schnelle Fuchs", "de"),
+            ("El gato rápido", "es"),
+            ("O gato rápido", "pt"),
+            ("こんにちは", "ja"),
+            ("你好", "zh"),
+            ("مرحبا", "ar"),
+            ("नमस्ते", "hi"),
+            ("안녕하세요", "ko"),
+            ("Здравствуйте", "ru"),
+        ];
+
+        for (text, expected_lang) in test_cases {
+            match detector.detect(text) {
+                Ok(lang) => assert_eq!(
+                    lang, expected_lang,
+                    "Failed to detect {} for text: {}",
+                    expected_lang, text
+                ),
+                Err(err) => panic!(
+                    "Failed to detect language for '{}': {:?}",
+                    text, err
+                ),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_language_detection() {
+        let detector = LanguageDetector::new();
+        let test_cases = vec![
+            ("The quick brown fox", "en"),
+            ("Le chat noir", "fr"),
+            ("Der schnelle Fuchs", "de"),
+            ("El gato rápido", "es"),
+            ("O gato rápido", "pt"),
+            ("こんにちは", "ja"),
+            ("你好", "zh"),
+            ("مرحبا", "ar"),
+            ("नमस्ते", "hi"),
+            ("안녕하세요", "ko"),
+            ("Здравствуйте", "ru"),
+        ];
+
+        for (text, expected_lang) in test_cases {
+            match detector.detect_async(text).await {
+                Ok(lang) => assert_eq!(
+                    lang, expected_lang,
+                    "Failed to detect {} for text: {}",
+                    expected_lang, text
+                ),
+                Err(err) => panic!(
+                    "Failed to detect language asynchronously for '{}': {:?}",
+                    text, err
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let detector = LanguageDetector::new();
+        assert!(
+            detector.detect("").is_err(),
+            "Empty input should return an error."
+        );
+        assert!(
+            detector.detect("   ").is_err(),
+            "Whitespace-only input should return an error."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_empty_input() {
+        let detector = LanguageDetector::new();
+        assert!(
+            detector.detect_async("").await.is_err(),
+            "Empty input should return an error in async detection."
+        );
+        assert!(
+            detector.detect_async("   ").await.is_err(),
+            "Whitespace-only input should return an error in async detection."
+        );
+    }
+
+    #[test]
+    fn test_non_linguistic_characters() {
+        let detector = LanguageDetector::new();
+        assert!(detector.detect("12345 @#$% !").is_err(), "Non-linguistic characters should return a detection error.");
+    }
+
+    #[tokio::test]
+    async fn test_async_non_linguistic_characters() {
+        let detector = LanguageDetector::new();
+        assert!(
+            detector.detect_async("12345 @#$% !").await.is_err(),
+            "Non-linguistic characters should return a detection error in async detection."
+        );
+    }
+
+    #[test]
+    fn test_mixed_language() {
+        let detector = LanguageDetector::new();
+        let result = detector
+            .detect("The quick brown fox Le chat noir")
+            .unwrap();
+        assert!(
+            result == "en" || result == "fr",
+            "Should detect either English or French in mixed-language input."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_mixed_language() {
+        let detector = LanguageDetector::new();
+        let result = detector
+            .detect_async("The quick brown fox Le chat noir")
+            .await
+            .unwrap();
+        assert!(
+            result == "en" || result == "fr",
+            "Should detect either English or French in mixed-language input asynchronously."
+        );
+    }
+
+    #[test]
+    fn test_long_input() {
+        let detector = LanguageDetector::new();
+        let long_text =
+            "The quick brown fox jumps over the lazy dog ".repeat(1000);
+        assert_eq!(
+            detector.detect(&long_text).unwrap(),
+            "en",
+            "Failed to detect English in long input."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_long_input() {
+        let detector = LanguageDetector::new();
+        let long_text = "Le chat noir est très beau ".repeat(1000);
+        assert_eq!(
+            detector.detect_async(&long_text).await.unwrap(),
+            "fr",
+            "Failed to detect French in long input asynchronously."
+        );
+    }
+
+    #[test]
+    fn test_added_language_patterns() {
+        let detector = LanguageDetector::new();
+        let test_cases = [
+            ("Ciao, grazie mille", "it"),
+            ("Hallo, hoe gaat het?", "nl"),
+            ("Het is een mooie dag", "nl"),
+            ("שלום עולם", "he"),
+            ("Terima kasih", "id"),
+            ("Saya tidak tahu", "id"),
+        ];
+        for (text, expected) in test_cases {
+            assert_eq!(
+                detector.detect(text).unwrap(),
+                expected,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_lang_code() {
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.convert_lang_code(Lang::Eng), "en");
+        assert_eq!(detector.convert_lang_code(Lang::Fra), "fr");
+        assert_eq!(detector.convert_lang_code(Lang::Deu), "de");
+        assert_eq!(detector.convert_lang_code(Lang::Spa), "es");
+        assert_eq!(detector.convert_lang_code(Lang::Por), "pt");
+        assert_eq!(detector.convert_lang_code(Lang::Jpn), "ja");
+        assert_eq!(detector.convert_lang_code(Lang::Cmn), "zh");
+        assert_eq!(detector.convert_lang_code(Lang::Ara), "ar");
+        assert_eq!(detector.convert_lang_code(Lang::Hin), "hi");
+        assert_eq!(detector.convert_lang_code(Lang::Kor), "ko");
+        assert_eq!(detector.convert_lang_code(Lang::Rus), "ru");
+        assert_eq!(detector.convert_lang_code(Lang::Ita), "it");
+        assert_eq!(detector.convert_lang_code(Lang::Nld), "nl");
+        assert_eq!(detector.convert_lang_code(Lang::Heb), "he");
+        assert_eq!(detector.convert_lang_code(Lang::Ind), "id");
+        // Test a language not explicitly handled
+        assert_eq!(detector.convert_lang_code(Lang::Tur), "tur");
+    }
+
+    #[test]
+    fn test_fold_diacritics() {
+        assert_eq!(
+            fold_diacritics("café résumé naïve"),
+            "cafe resume naive"
+        );
+        assert_eq!(fold_diacritics("Ångström Ŝ"), "Angstrom S");
+        assert_eq!(fold_diacritics("cafe\u{301}"), "cafe");
+        assert_eq!(fold_diacritics("știință"), "stiinta");
+        assert_eq!(fold_diacritics("Việt Nam đẹp"), "Viet Nam đep");
+        assert_eq!(fold_diacritics("ǎǐǒǔ"), "aiou");
+        // Letters without a decomposition are kept
+        assert_eq!(fold_diacritics("Straße Łódź"), "Straße Łodz");
+        // Marks in other scripts are stripped, syllables recomposed
+        assert_eq!(fold_diacritics("مَرحبا"), "مرحبا");
+        assert_eq!(fold_diacritics("안녕하세요 が"), "안녕하세요 か");
+        assert!(matches!(fold_diacritics("plain"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_detect_normalized() {
+        let detector = LanguageDetector::new();
+        let text = "Le cafe\u{301} est de\u{301}licieux";
+        assert_eq!(detector.detect_normalized(text).unwrap(), "fr");
+        assert_eq!(
+            detector.detect_normalized("Привет мир").unwrap(),
+            "ru"
+        );
+        assert_eq!(
+            detector.detect_normalized("안녕하세요 세계").unwrap(),
+            "ko"
+        );
+        assert!(detector.detect_normalized("\u{301}\u{301}").is_err());
+    }
+
+    #[test]
+    fn test_neutral_symbols() {
+        let detector =
+            LanguageDetector::new().with_neutral_symbols(true);
+        assert_eq!(detector.detect("🎉🎉 Bonjour 🎉").unwrap(), "fr");
+        assert_eq!(detector.detect("👍🏽 Danke schön").unwrap(), "de");
+        assert!(matches!(
+            detector.detect("🎉 👍🏽 ❤️"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+        assert_eq!(strip_symbols("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_monolingual_score() {
+        let detector = LanguageDetector::new();
+        assert_eq!(
+            detector.monolingual_score("Le chat, la maison.").unwrap(),
+            1.0
+        );
+        let mixed = detector
+            .monolingual_score("the cat le chat der Hund")
+            .unwrap();
+        assert!(mixed > 0.0 && mixed < 1.0);
+        assert!(matches!(
+            detector.monolingual_score("12345 !!!"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_leading_bom_is_ignored() {
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.detect("\u{FEFF}Bonjour").unwrap(), "fr");
+        assert!(detector.detect("\u{FEFF}").is_err());
+    }
+
+    #[test]
+    fn test_min_words_for_statistical() {
+        let lenient = LanguageDetector::new();
+        let strict =
+            LanguageDetector::new().with_min_words_for_statistical(3);
+
+        // A single word outside the keyword patterns is guessed by
+        // default but rejected when it is below the minimum word count.
+        assert!(lenient.detect("Straße").is_ok());
+        assert!(matches!(
+            strict.detect("Straße"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+
+        // Keyword patterns are unaffected by the minimum.
+        assert_eq!(strict.detect("Danke").unwrap(), "de");
+    }
+
+    #[test]
+    fn test_detect_excluding() {
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.detect("你好").unwrap(), "zh");
+        assert!(detector.detect_excluding("你好", &["zh"]).is_err());
+        assert_eq!(
+            detector.detect_excluding("la casa", &["fr"]).unwrap(),
+            "es"
+        );
+        assert_eq!(
+            detector.detect_excluding("Le chat noir", &[]).unwrap(),
+            "fr"
+        );
+    }
+
+    #[test]
+    fn test_detect_with_margin() {
+        let detector = LanguageDetector::new();
+        let (lang, margin) = detector
+            .detect_with_margin("Le chat et la maison")
+            .unwrap();
+        assert_eq!(lang, "fr");
+        assert!(margin > 0.0 && margin <= 1.0);
+
+        let (_, tied) =
+            detector.detect_with_margin("hello bonjour").unwrap();
+        assert_eq!(tied, 0.0);
+
+        assert!(detector.detect_with_margin("").is_err());
+    }
+
+    #[test]
+    fn test_keyword_confidence() {
+        let detector = LanguageDetector::new();
+        let (lang, dense) = detector
+            .keyword_confidence("le chat dans la maison")
+            .unwrap();
+        assert_eq!(lang, "fr");
+        assert!((dense - 0.6).abs() < f64::EPSILON);
+
+        // Spanish has more distinct keyword hits than French here.
+        let (lang, _) = detector
+            .keyword_confidence("la casa de los niños para la playa")
+            .unwrap();
+        assert_eq!(lang, "es");
+
+        assert!(matches!(
+            detector.keyword_confidence("xyzzy"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_shared_keywords_follow_density() {
+        let detector = LanguageDetector::new();
+        let (lang, _) = detector
+            .detect_with_margin("la casa de los niños para la playa")
+            .unwrap();
+        assert_eq!(lang, "es");
+    }
+
+    #[test]
+    fn test_detect_with_confidence() {
+        let detector = LanguageDetector::new();
+        let result =
+            detector.detect_with_confidence("Привет мир").unwrap();
+        assert_eq!(result.language, "ru");
+        assert_eq!(result.script, Some(Script::Cyrillic));
+        assert!(result.confidence > 0.0);
+        assert!(detector.detect_with_confidence("").is_err());
+    }
+
+    #[test]
+    fn test_with_pipeline() {
+        let regex_only = LanguageDetector::new()
+            .with_pipeline(&[Stage::KeywordRegex, Stage::ScriptMatch]);
+        assert_eq!(regex_only.detect("Le chat").unwrap(), "fr");
+        assert_eq!(regex_only.detect("Привет").unwrap(), "ru");
+        assert!(regex_only.detect("Straße").is_err());
+
+        // Short keyword phrases need the keyword stage; long sentences are
+        // settled by whatlang when it runs first.
+        let whole_first = LanguageDetector::new().with_pipeline(&[
+            Stage::WhatlangWhole,
+            Stage::KeywordRegex,
+        ]);
+        let whole_only = LanguageDetector::new()
+            .with_pipeline(&[Stage::WhatlangWhole]);
+        assert_eq!(whole_first.detect("Le chat noir").unwrap(), "fr");
+        assert!(whole_only.detect("Le chat noir").is_err());
+        let text = "la casa es muy grande y bonita, y tiene un jardín con muchas flores";
+        assert_eq!(whole_first.detect(text).unwrap(), "es");
+        assert_eq!(whole_only.detect(text).unwrap(), "es");
+
+        let empty = LanguageDetector::new().with_pipeline(&[]);
+        assert!(empty.detect("Hello").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_whatlang_detector() {
+        let detector = WhatlangDetector::default();
+        let text =
+            "Der schnelle braune Fuchs springt über den faulen Hund";
+        assert_eq!(detector.detect(text).unwrap(), "de");
+        assert_eq!(detector.detect_async(text).await.unwrap(), "de");
+
+        // Keyword matches alone are not enough without the patterns
+        assert!(LanguageDetector::new().detect("Le chat noir").is_ok());
+        assert!(matches!(
+            detector.detect("Le chat noir"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_incremental_detector() {
+        let mut detector = IncrementalDetector::new();
+        detector.push_word("123");
+        detector.push_word("...");
+        assert_eq!(detector.current_best(), None);
+
+        detector.push_word("Hello,");
+        assert_eq!(
+            detector.current_best(),
+            Some(("en".to_string(), 1.0))
+        );
+
+        for word in ["le", "chat", "et", "la", "souris"] {
+            detector.push_word(word);
+        }
+        let (lang, share) = detector.current_best().unwrap();
+        assert_eq!(lang, "fr");
+        assert!(share > 0.5 && share < 1.0);
+    }
+
+    #[test]
+    fn test_confidence_threshold() {
+        let detector = LanguageDetector::new();
+        assert_eq!(
+            detector
+                .clone()
+                .with_confidence_threshold(0.7)
+                .confidence_threshold(),
+            0.7
+        );
+        assert_eq!(
+            detector
+                .with_confidence_threshold(2.0)
+                .confidence_threshold(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_reconciliation() {
+        let text = "la casa es muy grande y bonita, y tiene un jardín con muchas flores";
+        let detector =
+            LanguageDetector::new().with_reconciliation(true);
+        let explanation = detector.explain(text).unwrap();
+        assert_eq!(explanation.language, "es");
+        assert_eq!(explanation.stage, Stage::WhatlangWhole);
+        assert_eq!(explanation.matched, None);
+
+        // Strong keyword evidence is not overridden
+        assert_eq!(
+            detector.detect("Le chat et le chien").unwrap(),
+            "fr"
+        );
+        assert_eq!(
+            detector.detect_excluding(text, &["es"]).unwrap(),
+            "fr"
+        );
+    }
+
+    #[test]
+    fn test_detect_candidates() {
+        let detector = LanguageDetector::new();
+        let candidates = detector
+            .detect_candidates("Le chat et le chien, the dog", 5)
+            .unwrap();
+        assert_eq!(candidates[0].0, "fr");
+        assert!(candidates[0].1 > candidates[1].1);
+        assert!(candidates.iter().any(|(lang, _)| lang == "en"));
+
+        let top = detector
+            .detect_candidates("Hello bonjour hallo", 1)
+            .unwrap();
+        assert_eq!(top, vec![("en".to_string(), 1.0 / 3.0)]);
+        assert!(matches!(
+            detector.detect_candidates("", 3),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+        assert!(detector.detect_candidates("123 456", 3).is_err());
+    }
+
+    #[test]
+    fn test_language_distribution() {
+        let detector = LanguageDetector::new();
+        for text in [
+            "The cat and the dog",
+            "Привет мир",
+            "la casa es muy bonita",
+        ] {
+            let distribution =
+                detector.language_distribution(text).unwrap();
+            let total: f64 = distribution.values().sum();
+            assert!((total - 1.0).abs() < 1e-9, "{}", text);
+            assert!(distribution
+                .values()
+                .all(|p| *p > 0.0 && *p <= 1.0));
+        }
+
+        let distribution =
+            detector.language_distribution("Привет мир").unwrap();
+        assert!(distribution["ru"] > 0.5);
+        assert!(matches!(
+            detector.language_distribution("12345"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_identifier_words() {
+        assert_eq!(
+            identifier_words("le_rapport-2023.pdf"),
+            "le rapport"
+        );
+        assert_eq!(identifier_words("derBericht"), "der Bericht");
+        assert_eq!(identifier_words("Der Hund"), "Der Hund");
+        assert_eq!(identifier_words(".bashrc"), "bashrc");
+        assert_eq!(identifier_words("v1.2.3"), "");
+    }
+
+    #[test]
+    fn test_detect_short() {
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.detect_short("Привет").unwrap(), "ru");
+        assert_eq!(
+            detector.detect_short("the-annual-report").unwrap(),
+            "en"
+        );
+        assert_eq!(detector.detect_short("Le Chat").unwrap(), "fr");
+        assert!(matches!(
+            detector.detect_short("IMG_2041.jpg"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+        assert!(detector.detect_short("").is_err());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let detector = LanguageDetector::new();
+        let report = detector.evaluate(&[
+            ("The cat", "en"),
+            ("The dog", "en"),
+            ("Le chat", "fr"),
+            ("Le chien", "en"),
+            ("42", "de"),
+        ]);
+        assert_eq!(report.accuracy, 0.6);
+        assert_eq!(
+            report.per_language["en"],
+            LanguageMetrics {
+                precision: 1.0,
+                recall: 2.0 / 3.0,
+            }
+        );
+        assert_eq!(report.per_language["fr"].precision, 0.5);
+        assert_eq!(report.per_language["fr"].recall, 1.0);
+        assert_eq!(report.per_language["de"].recall, 0.0);
+        assert_eq!(report.per_language["und"].precision, 0.0);
+        assert_eq!(
+            report.confusion[&("en".to_string(), "fr".to_string())],
+            1
+        );
+        assert_eq!(
+            report.confusion[&("de".to_string(), "und".to_string())],
+            1
+        );
+
+        assert_eq!(detector.evaluate(&[]), EvalReport::default());
+    }
+
+    #[test]
+    fn test_grapheme_prefix() {
+        assert_eq!(grapheme_prefix("hello", 3), "hel");
+        assert_eq!(grapheme_prefix("hello", 10), "hello");
+        assert_eq!(grapheme_prefix("", 3), "");
+
+        // Combining diacritical marks stay with their base letter
+        assert_eq!(
+            grapheme_prefix("cafe\u{301} noir", 4),
+            "cafe\u{301}"
+        );
+        assert_eq!(
+            grapheme_prefix("a\u{323}\u{302}bc", 1),
+            "a\u{323}\u{302}"
+        );
+
+        // Zero-width-joiner sequences and skin tone modifiers stay whole
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let text = format!("{} ok", family);
+        assert_eq!(grapheme_prefix(&text, 1), family);
+        assert_eq!(grapheme_prefix(&text, 2), family);
+        assert_eq!(
+            grapheme_prefix("\u{1F44D}\u{1F3FD}!", 1),
+            "\u{1F44D}\u{1F3FD}"
+        );
+
+        // Devanagari vowel signs are not cut from their consonant
+        assert_eq!(grapheme_prefix("नमस्ते", 3), "नमस्ते");
+        assert_eq!(grapheme_prefix("नमो नमः", 2), "नमो");
+
+        // Regional indicator pairs are not split into half a flag
+        assert_eq!(
+            grapheme_prefix("\u{1F1EB}\u{1F1F7}\u{1F1E9}\u{1F1EA}", 1),
+            "\u{1F1EB}\u{1F1F7}"
+        );
+
+        // Hebrew points and Thai vowel marks stay with their letter
+        assert_eq!(grapheme_prefix("שָׁלוֹם", 1), "שָׁ");
+        assert_eq!(grapheme_prefix("ที่นี่", 1), "ที่");
+    }
+
+    #[test]
+    fn test_max_prefix() {
+        let detector = LanguageDetector::new().with_max_prefix(8);
+        assert_eq!(
+            detector.detect("Der Hund. The cat and the dog").unwrap(),
+            "de"
+        );
+        assert!(LanguageDetector::new()
+            .with_max_prefix(3)
+            .detect("123 Bonjour")
+            .is_err());
+    }
+
+    #[test]
+    fn test_detect_consistent() {
+        let detector = LanguageDetector::new();
+        assert_eq!(
+            detector
+                .detect_consistent(&[
+                    "Der Hund",
+                    "Die Katze",
+                    "Das Haus"
+                ])
+                .unwrap(),
+            "de"
+        );
+        assert_eq!(
+            detector
+                .detect_consistent(&["The cat", "Le chat", "The dog"]),
+            Err(I18nError::InconsistentLanguages(vec![
+                "en".to_string(),
+                "fr".to_string()
+            ]))
+        );
+        assert_eq!(
+            detector.detect_consistent(&["The cat", "123"]),
+            Err(I18nError::LanguageDetectionFailed)
+        );
+        assert_eq!(
+            detector.detect_consistent(&[]),
+            Err(I18nError::LanguageDetectionFailed)
+        );
+    }
 
-        for (text, expected_lang) in test_cases {
-            match detector.detect(text) {
-                Ok(lang) => assert_eq!(
-                    lang, expected_lang,
-                    "Failed to detect {} for text: {}",
-                    expected_lang, text
-                ),
-                Err(err) => panic!(
-                    "Failed to detect language for '{}': {:?}",
-                    text, err
-                ),
-            }
-        }
+    #[test]
+    fn test_default_language_for_script() {
+        assert_eq!(
+            default_language_for_script(Script::Arabic),
+            Some("ar")
+        );
+        assert_eq!(
+            default_language_for_script(Script::Devanagari),
+            Some("hi")
+        );
+        assert_eq!(
+            default_language_for_script(Script::Hiragana),
+            Some("ja")
+        );
+        assert_eq!(default_language_for_script(Script::Han), None);
+        assert_eq!(default_language_for_script(Script::Latin), None);
+
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.detect("שלום עולם").unwrap(), "he");
     }
 
     #[tokio::test]
-    async fn test_async_language_detection() {
+    async fn test_detect_batch() {
         let detector = LanguageDetector::new();
-        let test_cases = vec![
-            ("The quick brown fox", "en"),
-            ("Le chat noir", "fr"),
-            ("Der schnelle Fuchs", "de"),
-            ("El gato rápido", "es"),
-            ("O gato rápido", "pt"),
-            ("こんにちは", "ja"),
-            ("你好", "zh"),
-            ("مرحبا", "ar"),
-            ("नमस्ते", "hi"),
-            ("안녕하세요", "ko"),
-            ("Здравствуйте", "ru"),
-        ];
+        let texts: Vec<String> = (0..10)
+            .map(|i| match i % 3 {
+                0 => format!("The cat {}", i),
+                1 => format!("Le chat {}", i),
+                _ => i.to_string(),
+            })
+            .collect();
+        let texts: Vec<&str> =
+            texts.iter().map(String::as_str).collect();
 
-        for (text, expected_lang) in test_cases {
-            match detector.detect_async(text).await {
-                Ok(lang) => assert_eq!(
-                    lang, expected_lang,
-                    "Failed to detect {} for text: {}",
-                    expected_lang, text
-                ),
-                Err(err) => panic!(
-                    "Failed to detect language asynchronously for '{}': {:?}",
-                    text, err
-                ),
-            }
+        let results = detector.detect_batch(&texts);
+        assert_eq!(results.len(), 10);
+        assert_eq!(results[3].as_deref(), Ok("en"));
+        assert_eq!(results[4].as_deref(), Ok("fr"));
+        assert!(results[5].is_err());
+
+        assert_eq!(detector.detect_batch_async(&texts).await, results);
+        assert!(detector.detect_batch_async(&[]).await.is_empty());
+    }
+
+    #[test]
+    fn test_detect_as() {
+        let detector = LanguageDetector::new();
+        let cases = [
+            ("Der Hund", "de", "deu", "de-DE"),
+            ("こんにちは", "ja", "jpn", "ja-JP"),
+            ("Привет мир", "ru", "rus", "ru-RU"),
+            ("Ciao, grazie mille", "it", "ita", "it-IT"),
+            ("Het is een mooie dag", "nl", "nld", "nl-NL"),
+            ("שלום עולם", "he", "heb", "he-IL"),
+            ("Terima kasih", "id", "ind", "id-ID"),
+        ];
+        for (text, iso1, iso3, bcp47) in cases {
+            assert_eq!(
+                detector
+                    .detect_as(text, LangCodeFormat::Iso6391)
+                    .unwrap(),
+                iso1
+            );
+            assert_eq!(
+                detector
+                    .detect_as(text, LangCodeFormat::Iso6393)
+                    .unwrap(),
+                iso3
+            );
+            assert_eq!(
+                detector
+                    .detect_as(text, LangCodeFormat::Bcp47)
+                    .unwrap(),
+                bcp47
+            );
         }
+        assert!(detector.detect_as("", LangCodeFormat::Bcp47).is_err());
     }
 
     #[test]
-    fn test_empty_input() {
+    fn test_add_pattern() {
+        let original = LanguageDetector::new();
+        let mut detector = original.clone();
+        detector.add_pattern(r"(?i)\b(xin|chào)\b", "vi").unwrap();
+        assert_eq!(detector.detect("xin chào").unwrap(), "vi");
+        assert_ne!(
+            original.detect("xin chào").ok().as_deref(),
+            Some("vi")
+        );
+
+        // Appended patterns come after the built-in ones
+        detector.add_pattern(r"(?i)\bthe\b", "xx").unwrap();
+        assert_eq!(detector.detect("the house").unwrap(), "en");
+        assert!(matches!(
+            detector.add_pattern("[", "xx"),
+            Err(I18nError::UnexpectedError(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let detector = LanguageDetector::new()
+            .with_custom_pattern(r"(?i)\bhej\b", "sv")
+            .unwrap();
+        assert_eq!(detector.detect("Hej the world").unwrap(), "sv");
+        assert_eq!(
+            LanguageDetector::new().detect("Hej the").unwrap(),
+            "en"
+        );
+
+        assert!(matches!(
+            LanguageDetector::new().with_custom_pattern("(", "xx"),
+            Err(I18nError::UnexpectedError(_))
+        ));
+        assert!(LanguageDetector::new()
+            .with_pattern_limits(100, 100)
+            .with_custom_pattern(r"\p{L}{50}", "xx")
+            .is_err());
+    }
+
+    #[test]
+    fn test_explain() {
         let detector = LanguageDetector::new();
-        assert!(
-            detector.detect("").is_err(),
-            "Empty input should return an error."
+        let explanation =
+            detector.explain("  Wir sagen danke").unwrap();
+        assert_eq!(explanation.language, "de");
+        assert_eq!(
+            explanation.matched,
+            Some(KeywordMatch {
+                keyword: "danke".to_string(),
+                range: 12..17,
+            })
         );
-        assert!(
-            detector.detect("   ").is_err(),
-            "Whitespace-only input should return an error."
+
+        let explanation = detector.explain("Привет мир").unwrap();
+        assert_eq!(explanation.stage, Stage::ScriptMatch);
+        assert_eq!(
+            explanation.matched,
+            Some(KeywordMatch {
+                keyword: "Привет".to_string(),
+                range: 0..12,
+            })
         );
+
+        let explanation = detector.explain("Straße").unwrap();
+        assert_eq!(explanation.stage, Stage::WhatlangWords);
+        assert_eq!(explanation.matched, None);
     }
 
-    #[tokio::test]
-    async fn test_async_empty_input() {
+    #[test]
+    fn test_explain_examined_text() {
+        let detector = LanguageDetector::new().with_max_prefix(3);
+        let explanation = detector.explain("Привет мир").unwrap();
+        assert_eq!(explanation.language, "ru");
+        assert_eq!(
+            explanation.matched,
+            Some(KeywordMatch {
+                keyword: "При".to_string(),
+                range: 0..6,
+            })
+        );
+        assert_eq!(
+            detector.detect_explained("Привет мир").unwrap().range,
+            Some(0..6)
+        );
+
+        let detector =
+            LanguageDetector::new().with_neutral_symbols(true);
+        let text = "\u{1F600} Bon\u{200B}jour mes amis";
+        let matched = detector.explain(text).unwrap().matched.unwrap();
+        assert_eq!(matched.keyword, "Bon\u{200B}jour");
+        assert_eq!(matched.range, 5..15);
+        assert_eq!(input_range("  abc", "abc", 1..3), 3..5);
+    }
+
+    #[test]
+    fn test_detect_explained() {
         let detector = LanguageDetector::new();
-        assert!(
-            detector.detect_async("").await.is_err(),
-            "Empty input should return an error in async detection."
+        assert_eq!(
+            detector.detect_explained("  Wir sagen danke").unwrap(),
+            Detection {
+                code: "de".to_string(),
+                method: DetectionMethod::Pattern,
+                range: Some(12..17),
+            }
         );
-        assert!(
-            detector.detect_async("   ").await.is_err(),
-            "Whitespace-only input should return an error in async detection."
+        assert_eq!(
+            detector.detect_explained("Straße").unwrap(),
+            Detection {
+                code: "de".to_string(),
+                method: DetectionMethod::Whatlang,
+                range: None,
+            }
         );
+        assert!(detector.detect_explained("").is_err());
     }
 
     #[test]
-    fn test_non_linguistic_characters() {
+    fn test_has_mixed_scripts() {
+        assert!(!has_mixed_scripts("Hello world"));
+        assert!(!has_mixed_scripts("こんにちは世界、カタカナ"));
+        assert!(has_mixed_scripts("Hello Привет"));
+        assert!(!has_mixed_scripts("123 !!!"));
+    }
+
+    #[test]
+    fn test_detect_with_warnings() {
+        use DetectionWarning::*;
         let detector = LanguageDetector::new();
-        assert!(detector.detect("12345 @#$% !").is_err(), "Non-linguistic characters should return a detection error.");
+
+        let (_, warnings) = detector
+            .detect_with_warnings("The cat and the dog")
+            .unwrap();
+        assert!(warnings.is_empty());
+
+        let (lang, warnings) =
+            detector.detect_with_warnings("Straße").unwrap();
+        assert_eq!(lang, "de");
+        assert_eq!(warnings, [TooShort, WordByWordFallbackUsed]);
+
+        let (lang, warnings) = detector
+            .detect_with_warnings("the Привет мир друзья")
+            .unwrap();
+        assert_eq!(lang, "ru");
+        assert_eq!(warnings, [MixedScripts]);
+
+        let (_, warnings) =
+            detector.detect_with_warnings("the Привет مرحبا").unwrap();
+        assert!(warnings.contains(&MixedScripts));
+        assert!(warnings.contains(&LowConfidence));
+
+        assert!(detector.detect_with_warnings("").is_err());
     }
 
-    #[tokio::test]
-    async fn test_async_non_linguistic_characters() {
+    #[test]
+    fn test_keyword_hits_are_weighted() {
         let detector = LanguageDetector::new();
-        assert!(
-            detector.detect_async("12345 @#$% !").await.is_err(),
-            "Non-linguistic characters should return a detection error in async detection."
+        assert_eq!(
+            detector
+                .detect("Le chat mange the croissant dans la cuisine")
+                .unwrap(),
+            "fr"
         );
+        // Ties go to the language whose pattern comes first.
+        assert_eq!(detector.match_patterns("la casa", &[]), Some("fr"));
+        assert_eq!(
+            detector.match_patterns("la casa", &["fr"]),
+            Some("es")
+        );
+        assert_eq!(detector.match_patterns("xyz", &[]), None);
     }
 
     #[test]
-    fn test_mixed_language() {
+    fn test_detect_reader() {
         let detector = LanguageDetector::new();
-        let result = detector
-            .detect("The quick brown fox Le chat noir")
-            .unwrap();
-        assert!(
-            result == "en" || result == "fr",
-            "Should detect either English or French in mixed-language input."
+        let document =
+            "Der Hund und die Katze schlafen.\n".repeat(50_000);
+        let mut reader = Cursor::new(document.as_bytes());
+        assert_eq!(detector.detect_reader(&mut reader).unwrap(), "de");
+        assert!(reader.position() <= STREAM_CHUNK_BYTES);
+
+        let short = Cursor::new("Hello world".as_bytes());
+        assert_eq!(detector.detect_reader(short).unwrap(), "en");
+        assert!(detector.detect_reader(Cursor::new(b"")).is_err());
+        assert!(matches!(
+            detector.detect_reader(Cursor::new(b"caf\xff the")),
+            Err(I18nError::UnexpectedError(_))
+        ));
+
+        assert_eq!(utf8_prefix("é".as_bytes()).unwrap(), "é");
+        assert_eq!(utf8_prefix(&"é".as_bytes()[..1]).unwrap(), "");
+    }
+
+    #[test]
+    fn test_detect_strict() {
+        let detector = LanguageDetector::new();
+        assert_eq!(detector.min_text_length, 0);
+        assert_eq!(
+            detector
+                .detect_strict("Le chat dort sur le canapé")
+                .unwrap(),
+            "fr"
         );
+
+        let detector = detector.with_min_text_length(3);
+        assert!(detector.detect_strict(" Hi ").is_err());
+        assert!(detector.detect("Hi").is_ok());
+
+        let picky =
+            LanguageDetector::new().with_confidence_threshold(1.0);
+        assert!(picky.detect_strict("Hello there").is_err());
+        assert!(picky.detect_strict("").is_err());
     }
 
-    #[tokio::test]
-    async fn test_async_mixed_language() {
+    #[test]
+    fn test_require_content_word() {
         let detector = LanguageDetector::new();
-        let result = detector
-            .detect_async("The quick brown fox Le chat noir")
-            .await
-            .unwrap();
-        assert!(
-            result == "en" || result == "fr",
-            "Should detect either English or French in mixed-language input asynchronously."
+        assert!(!detector.has_content_word("the a an of"));
+        assert!(!detector.has_content_word("Le, la, les!"));
+        assert!(detector.has_content_word("the cat"));
+        assert!(!detector.has_content_word("Привет"));
+
+        let strict = LanguageDetector::new()
+            .with_require_content_word(true)
+            .with_pipeline(&[Stage::ScriptMatch, Stage::KeywordRegex]);
+        assert!(strict.detect("le la les").is_err());
+        assert_eq!(strict.detect("le chat").unwrap(), "fr");
+        assert_eq!(strict.detect("Привет").unwrap(), "ru");
+        assert_eq!(
+            LanguageDetector::new().detect("le la les").unwrap(),
+            "fr"
         );
     }
 
     #[test]
-    fn test_long_input() {
+    fn test_detect_or_else() {
         let detector = LanguageDetector::new();
-        let long_text =
-            "The quick brown fox jumps over the lazy dog ".repeat(1000);
+        let lang = detector.detect_or_else("Bonjour", |_| {
+            panic!("fallback must not run")
+        });
+        assert_eq!(lang.unwrap(), "fr");
+
+        let lang = detector.detect_or_else("!!!", |text| {
+            assert_eq!(text, "!!!");
+            Ok("x-custom".to_string())
+        });
+        assert_eq!(lang.unwrap(), "x-custom");
+
+        assert!(matches!(
+            detector.detect_or_else("", |_| Err(
+                I18nError::UnexpectedError("remote down".to_string())
+            )),
+            Err(I18nError::UnexpectedError(_))
+        ));
+    }
+
+    #[test]
+    fn test_confidence_levels() {
+        assert_eq!(Confidence::from_score(1.0), Confidence::High);
+        assert_eq!(Confidence::from_score(0.75), Confidence::High);
+        assert_eq!(Confidence::from_score(0.74), Confidence::Medium);
+        assert_eq!(Confidence::from_score(0.4), Confidence::Medium);
+        assert_eq!(Confidence::from_score(0.0), Confidence::Low);
+        assert!(Confidence::High > Confidence::Low);
+
+        let detector = LanguageDetector::new();
+        let (_, level) = detector
+            .detect_leveled("the chat le chien die Katze")
+            .unwrap();
+        assert!(level < Confidence::High);
+        assert!(detector.detect_leveled("").is_err());
+    }
+
+    #[test]
+    fn test_detection_result_display() {
+        let result = DetectionResult {
+            language: "fr".to_string(),
+            confidence: 0.874,
+            script: Some(Script::Latin),
+        };
         assert_eq!(
-            detector.detect(&long_text).unwrap(),
-            "en",
-            "Failed to detect English in long input."
+            result.to_string(),
+            "fr (87% confidence, Latin script)"
+        );
+        assert_eq!(
+            format!("{:#}", result),
+            "language:   fr\nconfidence: 87%\nscript:     Latin"
+        );
+
+        let no_script = DetectionResult {
+            script: None,
+            ..result
+        };
+        assert_eq!(no_script.to_string(), "fr (87% confidence)");
+        assert_eq!(
+            format!("{:#}", no_script),
+            "language:   fr\nconfidence: 87%"
         );
     }
 
-    #[tokio::test]
-    async fn test_async_long_input() {
+    #[test]
+    fn test_with_thread_local() {
+        let first = LanguageDetector::with_thread_local(|d| {
+            Arc::as_ptr(&d.patterns)
+        });
+        let second = LanguageDetector::with_thread_local(|d| {
+            Arc::as_ptr(&d.patterns)
+        });
+        assert_eq!(first, second);
+
+        let other = std::thread::spawn(|| {
+            LanguageDetector::with_thread_local(|d| d.detect("Bonjour"))
+        })
+        .join()
+        .unwrap();
+        assert_eq!(other.unwrap(), "fr");
+    }
+
+    #[test]
+    fn test_detect_chinese_variant() {
         let detector = LanguageDetector::new();
-        let long_text = "Le chat noir est très beau ".repeat(1000);
         assert_eq!(
-            detector.detect_async(&long_text).await.unwrap(),
-            "fr",
-            "Failed to detect French in long input asynchronously."
+            detector.detect_chinese_variant("我们说汉语"),
+            Some(ChineseVariant::Simplified)
+        );
+        assert_eq!(
+            detector.detect_chinese_variant("我們說漢語"),
+            Some(ChineseVariant::Traditional)
+        );
+        assert_eq!(
+            detector.detect_chinese_variant("这個"),
+            Some(ChineseVariant::Ambiguous)
         );
+        assert_eq!(
+            detector.detect_chinese_variant("你好"),
+            Some(ChineseVariant::Ambiguous)
+        );
+        assert_eq!(detector.detect_chinese_variant("привет"), None);
     }
 
     #[test]
-    fn test_convert_lang_code() {
+    fn test_script_language() {
+        assert_eq!(script_language("Привет мир"), Some("ru"));
+        assert_eq!(script_language("مرحبا بالعالم"), Some("ar"));
+        assert_eq!(script_language("你好世界"), Some("zh"));
+        assert_eq!(script_language("こんにちは世界"), Some("ja"));
+        assert_eq!(script_language("नमस्ते दुनिया"), Some("hi"));
+        assert_eq!(script_language("안녕하세요 세계"), Some("ko"));
+        assert_eq!(script_language("안녕하세요 OK"), None);
+        assert_eq!(script_language("Hello world"), None);
+        assert_eq!(script_language("Hello Привет"), None);
+        assert_eq!(script_language("123"), None);
+    }
+
+    #[test]
+    fn test_script_precheck_respects_exclusions() {
         let detector = LanguageDetector::new();
-        assert_eq!(detector.convert_lang_code(Lang::Eng), "en");
-        assert_eq!(detector.convert_lang_code(Lang::Fra), "fr");
-        assert_eq!(detector.convert_lang_code(Lang::Deu), "de");
-        assert_eq!(detector.convert_lang_code(Lang::Spa), "es");
-        assert_eq!(detector.convert_lang_code(Lang::Por), "pt");
-        assert_eq!(detector.convert_lang_code(Lang::Jpn), "ja");
-        assert_eq!(detector.convert_lang_code(Lang::Cmn), "zh");
-        assert_eq!(detector.convert_lang_code(Lang::Ara), "ar");
-        assert_eq!(detector.convert_lang_code(Lang::Hin), "hi");
-        assert_eq!(detector.convert_lang_code(Lang::Kor), "ko");
-        assert_eq!(detector.convert_lang_code(Lang::Rus), "ru");
-        // Test a language not explicitly handled
-        assert_eq!(detector.convert_lang_code(Lang::Ita), "ita");
+        assert_eq!(detector.detect("Привет, мир!").unwrap(), "ru");
+        assert!(detector.detect_excluding("你好", &["zh"]).is_err());
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let text = "one\ntwo  \n\n  \nthree\r\n";
+        let ranges = paragraphs(text);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(&text[ranges[0].clone()], "one\ntwo");
+        assert_eq!(&text[ranges[1].clone()], "three");
+        assert!(paragraphs("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_detect_segments() {
+        let detector = LanguageDetector::new();
+        let text =
+            "The cat\n\nLe chat\n\n12345\n\nLa maison\n\nThe end";
+
+        let segments = detector.detect_segments(text, false);
+        let languages: Vec<&str> =
+            segments.iter().map(|s| s.language.as_str()).collect();
+        assert_eq!(languages, ["en", "fr", "fr", "en"]);
+        assert_eq!(&text[segments[1].range.clone()], "Le chat");
+
+        // The undetectable paragraph does not break the French span.
+        let merged = detector.detect_segments(text, true);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(
+            &text[merged[1].range.clone()],
+            "Le chat\n\n12345\n\nLa maison"
+        );
+        assert!(detector.detect_segments("", true).is_empty());
+    }
+
+    #[test]
+    fn test_supported_scripts() {
+        let scripts = LanguageDetector::supported_scripts();
+        assert_eq!(scripts.len(), 9);
+        assert_eq!(scripts[0], Script::Latin);
+        for script in [
+            Script::Cyrillic,
+            Script::Arabic,
+            Script::Hebrew,
+            Script::Han,
+        ] {
+            assert!(scripts.contains(&script));
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        let patterns = builtin_patterns().unwrap();
+        assert_eq!(patterns.len(), PATTERN_SOURCES.len());
+        assert_eq!(patterns[0].1, "en");
+
+        let detector = LanguageDetector::try_new().unwrap();
+        assert_eq!(detector.patterns.len(), PATTERN_SOURCES.len());
+        assert_eq!(detector.detect("Hallo und danke").unwrap(), "de");
+    }
+
+    #[test]
+    fn test_pattern_sources() {
+        let sources = LanguageDetector::pattern_sources();
+        assert_eq!(sources.len(), PATTERN_SOURCES.len());
+        let codes: Vec<&str> =
+            sources.iter().map(|(lang, _)| lang.as_str()).collect();
+        assert_eq!(&codes[..3], &["en", "fr", "de"]);
+        assert!(sources.iter().any(
+            |(lang, src)| lang == "ru" && src.contains("Cyrillic")
+        ));
     }
 
     #[test]
@@ -7,6 +7,7 @@
 //! implementations of language detection methods.
 
 use crate::error::I18nError;
+use crate::strict_rejects;
 use async_trait::async_trait;
 use std::fmt;
 use std::fmt::Debug;
@@ -44,7 +45,7 @@ async fn detect_async(
 /// A struct to hold multiple language detectors.
 #[derive(Default)]
 pub struct CompositeLanguageDetector {
-    detectors: Vec<Box<dyn LanguageDetectorTrait>>,
+    detectors: Vec<(Box<dyn LanguageDetectorTrait>, f64)>,
 }
 
 impl Debug for CompositeLanguageDetector {
@@ -61,32 +62,130 @@ pub fn new() -> Self {
         }
     }
 
-    /// Adds a new detector to the composite.
+    /// Adds a new detector to the composite with a voting weight of 1.0.
     pub fn add_detector(
         &mut self,
         detector: Box<dyn LanguageDetectorTrait>,
     ) {
-        self.detectors.push(detector);
+        self.add_detector_weighted(detector, 1.0);
+    }
+
+    /// Adds a new detector to the composite with the given voting weight.
+    ///
+    /// The weight only affects [`detect_vote`](Self::detect_vote), where
+    /// the detector's vote counts `weight` times. The first-wins
+    /// [`detect`](Self::detect) ignores it. Negative and non-finite
+    /// weights (NaN or infinity) are treated as `0.0`, so such a
+    /// detector's vote adds nothing to the total of its language.
+    ///
+    /// # Arguments
+    ///
+    /// * `detector` - The detector to add.
+    /// * `weight` - How much the detector's vote counts, at least `0.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::CompositeLanguageDetector;
+    ///
+    /// let mut composite = CompositeLanguageDetector::new();
+    /// composite.add_detector_weighted(Box::new(LanguageDetector::new()), 2.0);
+    /// assert_eq!(composite.detect_vote("Bonjour le monde").unwrap(), "fr");
+    /// ```
+    pub fn add_detector_weighted(
+        &mut self,
+        detector: Box<dyn LanguageDetectorTrait>,
+        weight: f64,
+    ) {
+        let weight = if weight.is_finite() {
+            weight.max(0.0)
+        } else {
+            0.0
+        };
+        self.detectors.push((detector, weight));
     }
 
     /// Detects the language using all added detectors.
     pub fn detect(&self, text: &str) -> Result<String, I18nError> {
-        for detector in &self.detectors {
-            if let Ok(lang) = detector.detect(text) {
-                return Ok(lang);
+        for (detector, _) in &self.detectors {
+            match detector.detect(text) {
+                Ok(lang) if !strict_rejects(&lang) => return Ok(lang),
+                _ => {}
             }
         }
         Err(I18nError::LanguageDetectionFailed)
     }
 
+    /// Detects the language by majority vote of all added detectors.
+    ///
+    /// Every detector is queried and each successful detection counts as
+    /// a vote of the detector's weight. The language with the highest
+    /// total wins; ties go to the language voted for by the earliest
+    /// added detector.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The language with the most votes, or an error if no detector succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector::LanguageDetector;
+    /// use langweave::language_detector_trait::CompositeLanguageDetector;
+    ///
+    /// let mut composite = CompositeLanguageDetector::new();
+    /// composite.add_detector(Box::new(LanguageDetector::new()));
+    /// composite.add_detector(Box::new(LanguageDetector::new()));
+    /// assert_eq!(composite.detect_vote("Bonjour le monde").unwrap(), "fr");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return `I18nError::LanguageDetectionFailed` if
+    /// no detector identifies the language.
+    pub fn detect_vote(&self, text: &str) -> Result<String, I18nError> {
+        let mut votes: Vec<(String, f64)> = Vec::new();
+        for (lang, weight) in
+            self.detectors.iter().filter_map(|(detector, weight)| {
+                detector
+                    .detect(text)
+                    .ok()
+                    .filter(|lang| !strict_rejects(lang))
+                    .map(|lang| (lang, *weight))
+            })
+        {
+            match votes.iter_mut().find(|(l, _)| *l == lang) {
+                Some((_, total)) => *total += weight,
+                None => votes.push((lang, weight)),
+            }
+        }
+
+        votes
+            .into_iter()
+            .fold(None, |best: Option<(String, f64)>, (lang, total)| {
+                match best {
+                    Some((_, most)) if most >= total => best,
+                    _ => Some((lang, total)),
+                }
+            })
+            .map(|(lang, _)| lang)
+            .ok_or(I18nError::LanguageDetectionFailed)
+    }
+
     /// Detects the language asynchronously using all added detectors.
     pub async fn detect_async(
         &self,
         text: &str,
     ) -> Result<String, I18nError> {
-        for detector in &self.detectors {
-            if let Ok(lang) = detector.detect_async(text).await {
-                return Ok(lang);
+        for (detector, _) in &self.detectors {
+            match detector.detect_async(text).await {
+                Ok(lang) if !strict_rejects(&lang) => return Ok(lang),
+                _ => {}
             }
         }
         Err(I18nError::LanguageDetectionFailed)
@@ -126,6 +225,93 @@ fn test_composite_detector() {
         assert!(composite.detect("Это русский").is_err());
     }
 
+    struct FixedDetector(&'static str);
+
+    #[async_trait]
+    impl LanguageDetectorTrait for FixedDetector {
+        fn detect(&self, _text: &str) -> Result<String, I18nError> {
+            Ok(self.0.to_string())
+        }
+
+        async fn detect_async(
+            &self,
+            text: &str,
+        ) -> Result<String, I18nError> {
+            self.detect(text)
+        }
+    }
+
+    #[test]
+    fn test_composite_detector_vote() {
+        let mut composite = CompositeLanguageDetector::new();
+        composite.add_detector(Box::new(MockDetector));
+        composite.add_detector(Box::new(FixedDetector("fr")));
+        composite.add_detector(Box::new(FixedDetector("fr")));
+
+        // First-wins keeps the earliest successful detector
+        assert_eq!(composite.detect("This is English").unwrap(), "en");
+        assert_eq!(
+            composite.detect_vote("This is English").unwrap(),
+            "fr"
+        );
+
+        // Ties go to the language of the earliest detector
+        composite.add_detector(Box::new(MockDetector));
+        assert_eq!(
+            composite.detect_vote("This is English").unwrap(),
+            "en"
+        );
+
+        assert!(CompositeLanguageDetector::new()
+            .detect_vote("x")
+            .is_err());
+    }
+
+    #[test]
+    fn test_composite_detector_weighted_vote() {
+        let mut composite = CompositeLanguageDetector::new();
+        composite.add_detector(Box::new(FixedDetector("fr")));
+        composite.add_detector_weighted(Box::new(MockDetector), 2.0);
+        assert_eq!(
+            composite.detect_vote("This is English").unwrap(),
+            "en"
+        );
+
+        composite
+            .add_detector_weighted(Box::new(FixedDetector("fr")), 0.5);
+        assert_eq!(
+            composite.detect_vote("This is English").unwrap(),
+            "en"
+        );
+
+        composite
+            .add_detector_weighted(Box::new(FixedDetector("fr")), 0.5);
+        assert_eq!(
+            composite.detect_vote("This is English").unwrap(),
+            "fr"
+        );
+
+        // Weights do not change the first-wins detection
+        assert_eq!(composite.detect("This is English").unwrap(), "fr");
+
+        for weight in [-10.0, f64::NAN, f64::INFINITY] {
+            let mut composite = CompositeLanguageDetector::new();
+            composite.add_detector(Box::new(MockDetector));
+            composite.add_detector_weighted(
+                Box::new(FixedDetector("fr")),
+                weight,
+            );
+            composite.add_detector_weighted(
+                Box::new(FixedDetector("fr")),
+                weight,
+            );
+            assert_eq!(
+                composite.detect_vote("This is English").unwrap(),
+                "en"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_composite_detector_async() {
         let mut composite = CompositeLanguageDetector::new();
@@ -15,30 +15,58 @@
 use language_detector_trait::LanguageDetectorTrait;
 use log::debug;
 use once_cell::sync::Lazy;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use crate::error::I18nError;
 use crate::language_detector::LanguageDetector;
+use crate::script::Script;
 use crate::translator::Translator;
 
+/// The `caching_detector` module contains a language detector that caches results.
+pub mod caching_detector;
 /// The `error` module contains error types used by the library.
 pub mod error;
 /// The `language_detector` module contains a simple regex-based language detector.
 pub mod language_detector;
 /// The `language_detector_trait` module contains the `LanguageDetectorTrait` trait for extensibility.
 pub mod language_detector_trait;
+/// The `ngram_detector` module contains a character tri-gram language detector.
+pub mod ngram_detector;
+/// The `script` module contains the `Script` enum describing writing systems.
+pub mod script;
 /// The `translations` module contains translation functions for different languages.
 pub mod translations;
 /// The `translator` module contains a simple translation service using a predefined dictionary.
 pub mod translator;
+/// The `transliteration` module romanizes Cyrillic and Japanese kana text.
+#[cfg(feature = "transliteration")]
+pub mod transliteration;
 
 /// A module that re-exports commonly used items for convenience.
 pub mod prelude {
+    pub use crate::detect_direction;
     pub use crate::detect_language;
+    pub use crate::detect_script;
     pub use crate::error::I18nError;
     pub use crate::is_language_supported;
+    pub use crate::language_autonym;
+    pub use crate::language_name;
+    pub use crate::set_strict;
     pub use crate::supported_languages;
+    pub use crate::supported_languages_named;
+    pub use crate::t;
+    pub use crate::text_direction;
     pub use crate::translate;
+    pub use crate::translate_checked;
+    pub use crate::translate_full;
     pub use crate::translator::Translator;
+    pub use crate::with_language;
+    pub use crate::with_language_async;
+    pub use crate::Direction;
 }
 
 /// The current version of the langweave library.
@@ -48,152 +76,1148 @@ pub mod prelude {
 static LANGUAGE_DETECTOR: Lazy<LanguageDetector> =
     Lazy::new(LanguageDetector::new);
 
+/// Whether strict mode is enabled.
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables strict mode for the crate-level functions.
+///
+/// In strict mode `translate` never falls back to the original text, and
+/// detection functions reject any language outside `supported_languages`
+/// with `I18nError::UnsupportedLanguage` instead of returning it. This
+/// covers the crate-level functions, the `translations` module, the
+/// `LanguageDetector` methods and the bundled detectors: functions
+/// returning several languages leave unsupported ones out, and functions
+/// that fall back to the key omit untranslated keys instead. Strict mode
+/// is off by default and applies process-wide.
+///
+/// # Arguments
+///
+/// * `strict` - `true` to enable strict mode, `false` to restore the lenient defaults.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::{is_strict, set_strict, translate};
+///
+/// set_strict(true);
+/// assert!(is_strict());
+/// assert!(translate("fr", "Untranslated").is_err());
+///
+/// set_strict(false);
+/// assert_eq!(translate("fr", "Untranslated").unwrap(), "Untranslated");
+/// ```
+pub fn set_strict(strict: bool) {
+    STRICT.store(strict, Ordering::SeqCst);
+}
+
+/// Returns `true` if strict mode is enabled.
+///
+/// # Returns
+///
+/// `true` if `set_strict(true)` is in effect, `false` otherwise.
+pub fn is_strict() -> bool {
+    STRICT.load(Ordering::SeqCst)
+}
+
+/// Returns `true` if strict mode is enabled and the language is outside
+/// the supported set.
+pub(crate) fn strict_rejects(lang: &str) -> bool {
+    is_strict() && !is_language_supported(lang)
+}
+
+/// Rejects detected languages outside the supported set in strict mode.
+pub(crate) fn check_detected(
+    lang: String,
+) -> Result<String, I18nError> {
+    if strict_rejects(&lang) {
+        debug!("Strict mode rejected detected language: {}", lang);
+        return Err(I18nError::UnsupportedLanguage(lang));
+    }
+    Ok(lang)
+}
+
+/// What `translate_with_options` returns when no translation exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FallbackMode {
+    /// Return the original text unchanged.
+    ReturnOriginal,
+    /// Return the `I18nError::TranslationFailed` error.
+    ReturnError,
+    /// Return an empty string.
+    ReturnEmpty,
+}
+
+/// Options controlling `translate_with_options`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TranslateOptions {
+    /// What to return when no translation exists.
+    pub fallback: FallbackMode,
+}
+
+impl Default for TranslateOptions {
+    /// Returns options that fall back to the original text, like
+    /// `translate`.
+    fn default() -> Self {
+        TranslateOptions {
+            fallback: FallbackMode::ReturnOriginal,
+        }
+    }
+}
+
+/// Translates a given text to a specified language, with explicit options.
+///
+/// The options decide what happens when the text has no translation; an
+/// unsupported language is always an error.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the target language code (e.g., "en", "fr").
+/// * `text` - A string slice that holds the text to be translated.
+/// * `opts` - The options to apply.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated text, or the fallback value.
+/// * `Err(I18nError)` - An error if the translation fails and no fallback applies.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::{translate_with_options, FallbackMode, TranslateOptions};
+///
+/// let opts = TranslateOptions { fallback: FallbackMode::ReturnEmpty };
+/// assert_eq!(translate_with_options("fr", "Hello", opts).unwrap(), "Bonjour");
+/// assert_eq!(translate_with_options("fr", "Untranslated", opts).unwrap(), "");
+///
+/// let opts = TranslateOptions { fallback: FallbackMode::ReturnError };
+/// assert!(translate_with_options("fr", "Untranslated", opts).is_err());
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The specified language is not supported.
+/// * No translation exists and the fallback is `FallbackMode::ReturnError`.
+pub fn translate_with_options(
+    lang: &str,
+    text: &str,
+    opts: TranslateOptions,
+) -> Result<String, I18nError> {
+    let translator = translator_for(lang)?;
+    match (translator.translate(text), opts.fallback) {
+        (Ok(translation), _) => Ok(translation),
+        (Err(e), FallbackMode::ReturnError) => Err(e),
+        (Err(_), FallbackMode::ReturnOriginal) => Ok(text.to_string()),
+        (Err(_), FallbackMode::ReturnEmpty) => Ok(String::new()),
+    }
+}
+
 /// Translates a given text to a specified language.
 ///
+/// Missing translations fall back to the original text unless strict mode
+/// is enabled; use `translate_with_options` to choose another fallback.
+///
+/// # Arguments
+///
+/// * `lang` - The target language code (e.g., "en", "fr"), as a `&str`, `String` or `&String`.
+/// * `text` - The text to be translated, as a `&str`, `String` or `&String`.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated text.
+/// * `Err(I18nError)` - An error if the translation fails.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translate;
+///
+/// let result = translate("fr", "Hello");
+/// assert_eq!(result.unwrap(), "Bonjour");
+///
+/// let key = String::from("Goodbye");
+/// assert_eq!(translate("fr", key).unwrap(), "Au revoir");
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The specified language is not supported.
+/// * Strict mode is enabled and no translation exists for the text.
+pub fn translate(
+    lang: impl AsRef<str>,
+    text: impl AsRef<str>,
+) -> Result<String, I18nError> {
+    let fallback = if is_strict() {
+        FallbackMode::ReturnError
+    } else {
+        FallbackMode::ReturnOriginal
+    };
+    translate_with_options(
+        lang.as_ref(),
+        text.as_ref(),
+        TranslateOptions { fallback },
+    )
+}
+
+/// Translates a given text to a specified language, reporting missing translations.
+///
+/// Unlike `translate`, this function never falls back to the original text,
+/// so missing translations surface as errors instead of being shipped
+/// untranslated.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the target language code (e.g., "en", "fr").
+/// * `text` - A string slice that holds the text to be translated.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated text.
+/// * `Err(I18nError)` - An error if the translation fails.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translate_checked;
+/// use langweave::error::I18nError;
+///
+/// assert_eq!(translate_checked("fr", "Hello").unwrap(), "Bonjour");
+/// assert!(matches!(
+///     translate_checked("fr", "Untranslated"),
+///     Err(I18nError::TranslationFailed(_))
+/// ));
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The specified language is not supported.
+/// * No translation exists for the text.
+pub fn translate_checked(
+    lang: &str,
+    text: &str,
+) -> Result<String, I18nError> {
+    translator_for(lang)?.translate(text)
+}
+
+/// Translates a key, selecting its plural form and filling in arguments.
+///
+/// Keys loaded from gettext `msgid_plural` entries use the `msgstr[n]`
+/// form for `count`. Otherwise the translation may contain an ICU plural
+/// block such as
+/// `{count, plural, one{# new message} other{# new messages}}`; the branch
+/// for `count` is chosen with the language's plural rule, preferring exact
+/// `=N` branches, and `#` is replaced with the count. Then `{count}` and
+/// the `{name}` placeholders from `args` are substituted. The count is
+/// formatted with the language's digit grouping (e.g. `1,234` in English,
+/// `1.234` in German). Placeholders without a value are left intact.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the target language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+/// * `count` - The count selecting the plural form.
+/// * `args` - Named values for the message's placeholders.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The finished message.
+/// * `Err(I18nError)` - An error if the translation fails.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translate_full;
+/// use langweave::translations::update_key;
+///
+/// update_key(
+///     "en",
+///     "inbox_summary",
+///     "{name}, you have {count, plural, one{# new message} other{# new messages}}",
+/// );
+/// assert_eq!(
+///     translate_full("en", "inbox_summary", 3, &[("name", "Ada")]).unwrap(),
+///     "Ada, you have 3 new messages"
+/// );
+/// assert_eq!(
+///     translate_full("en", "inbox_summary", 1, &[("name", "Ada")]).unwrap(),
+///     "Ada, you have 1 new message"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The specified language is not supported.
+/// * No translation exists for the key.
+/// * The plural block is malformed or has no branch for the count.
+pub fn translate_full(
+    lang: &str,
+    key: &str,
+    count: u64,
+    args: &[(&str, &str)],
+) -> Result<String, I18nError> {
+    let translator = translator_for(lang)?;
+    let message = match translations::translate_plural(lang, key, count)
+    {
+        Ok(form) => form,
+        Err(_) => translator.translate(key)?,
+    };
+    let formatted = translations::format_integer(lang, count);
+    let message =
+        translations::apply_plural(lang, &message, count, &formatted)
+            .ok_or_else(|| {
+            I18nError::TranslationFailed(format!(
+                "{}:{} has no plural form for {}",
+                lang, key, count
+            ))
+        })?;
+
+    let mut all_args = args.to_vec();
+    all_args.push(("count", &formatted));
+    Ok(translations::interpolate(&message, &all_args))
+}
+
+/// Returns the shared translator for a supported language.
+fn translator_for(lang: &str) -> Result<Arc<Translator>, I18nError> {
+    if !is_language_supported(lang) {
+        return Err(I18nError::UnsupportedLanguage(lang.to_string()));
+    }
+
+    Translator::cached(lang).map_err(|e| {
+        I18nError::TranslationFailed(format!(
+            "Failed to create translator: {}",
+            e
+        ))
+    })
+}
+
+/// Detects the language of a given text using the composite language detector.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text to analyze
+///
+/// # Returns
+///
+/// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails
+///
+/// # Examples
+///
+/// ```
+/// use langweave::language_detector::LanguageDetector;
+/// use langweave::error::I18nError;
+/// use langweave::language_detector_trait::LanguageDetectorTrait;
+///
+/// async fn detect_language() -> Result<(), I18nError> {
+///     // Create a new language detector
+///     let detector = LanguageDetector::new();
+///
+///     // Detect language
+///     let lang = detector.detect_async("Hello, world!").await?;
+///     println!("Detected language: {}", lang);
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// * The input text is empty or contains only non-alphabetic characters.
+/// * The language detection process fails to identify a language with sufficient confidence.
+/// * Strict mode is enabled and the detected language is not supported.
+pub async fn detect_language(text: &str) -> Result<String, I18nError> {
+    debug!("Detecting language for: {}", text);
+
+    if text.trim().is_empty() {
+        return Err(I18nError::LanguageDetectionFailed);
+    }
+
+    // Try detecting the language for the whole text first
+    match LANGUAGE_DETECTOR.detect_async(text).await {
+        Ok(detected_lang) => {
+            debug!("Detected language: {}", detected_lang);
+            return check_detected(detected_lang);
+        }
+        // Strict mode rejected the language, so fail loudly
+        Err(e @ I18nError::UnsupportedLanguage(_)) => return Err(e),
+        Err(_) => {}
+    }
+
+    // Fallback: Return the first successfully detected language from word-by-word detection
+    for word in text.split_whitespace() {
+        match LANGUAGE_DETECTOR.detect_async(word).await {
+            Ok(detected_lang) => {
+                debug!(
+                    "Detected language from word '{}': {}",
+                    word, detected_lang
+                );
+                return check_detected(detected_lang);
+            }
+            Err(e @ I18nError::UnsupportedLanguage(_)) => {
+                return Err(e)
+            }
+            Err(_) => {}
+        }
+    }
+
+    // If no language is detected, return an error
+    Err(I18nError::LanguageDetectionFailed)
+}
+
+/// Detects the source language of each paragraph and translates it.
+///
+/// Paragraphs already written in the target language are returned
+/// unchanged. Others are translated with `translate_checked`, so
+/// paragraphs missing from the dictionary yield an error rather than the
+/// original text. Paragraphs whose language cannot be detected, or is
+/// rejected by strict mode, are reported with the undetermined language
+/// code `und`.
+///
+/// # Arguments
+///
+/// * `target` - A string slice that holds the target language code (e.g., "fr").
+/// * `paragraphs` - The source paragraphs, in document order.
+///
+/// # Returns
+///
+/// * `Vec<(String, Result<String, I18nError>)>` - Each paragraph's detected language paired with its translation attempt.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::localize_paragraphs;
+///
+/// let results = localize_paragraphs("fr", &["Hello", "Bonjour le monde"]);
+/// assert_eq!(results[0].0, "en");
+/// assert_eq!(results[0].1.as_deref().unwrap(), "Bonjour");
+/// assert_eq!(results[1].0, "fr");
+/// assert_eq!(results[1].1.as_deref().unwrap(), "Bonjour le monde");
+/// ```
+pub fn localize_paragraphs(
+    target: &str,
+    paragraphs: &[&str],
+) -> Vec<(String, Result<String, I18nError>)> {
+    paragraphs
+        .iter()
+        .map(|paragraph| {
+            match LANGUAGE_DETECTOR
+                .detect(paragraph)
+                .and_then(check_detected)
+            {
+                Ok(source) if source.eq_ignore_ascii_case(target) => {
+                    (source, Ok(paragraph.to_string()))
+                }
+                Ok(source) => {
+                    (source, translate_checked(target, paragraph))
+                }
+                Err(e) => ("und".to_string(), Err(e)),
+            }
+        })
+        .collect()
+}
+
+/// Detects the language of raw bytes, such as the contents of a file.
+///
+/// A UTF-8 byte order mark is stripped, and input starting with a UTF-16
+/// little- or big-endian byte order mark is decoded as UTF-16. Any other
+/// input is decoded as UTF-8, replacing invalid sequences.
+///
+/// # Arguments
+///
+/// * `bytes` - A byte slice that holds the encoded text to analyze.
+///
+/// # Returns
+///
+/// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::detect_language_bytes;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let utf8 = b"\xEF\xBB\xBFBonjour le monde";
+///     assert_eq!(detect_language_bytes(utf8).await.unwrap(), "fr");
+///
+///     let utf16: Vec<u8> = [0xFF, 0xFE]
+///         .into_iter()
+///         .chain("Hallo Welt".encode_utf16().flat_map(u16::to_le_bytes))
+///         .collect();
+///     assert_eq!(detect_language_bytes(&utf16).await.unwrap(), "de");
+/// }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the decoded text is empty or its
+/// language cannot be detected.
+pub async fn detect_language_bytes(
+    bytes: &[u8],
+) -> Result<String, I18nError> {
+    detect_language(&decode_text(bytes)).await
+}
+
+/// Decodes bytes into text, honouring UTF-8 and UTF-16 byte order marks.
+fn decode_text(bytes: &[u8]) -> Cow<'_, str> {
+    fn decode_utf16(bytes: &[u8], from: fn([u8; 2]) -> u16) -> String {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    }
+
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest),
+        [0xFF, 0xFE, rest @ ..] => {
+            Cow::Owned(decode_utf16(rest, u16::from_le_bytes))
+        }
+        [0xFE, 0xFF, rest @ ..] => {
+            Cow::Owned(decode_utf16(rest, u16::from_be_bytes))
+        }
+        _ => String::from_utf8_lossy(bytes),
+    }
+}
+
+/// Detects the language of a given text, falling back to a declared language.
+///
+/// The declared language typically comes from an HTTP `Content-Language`
+/// header or an HTML `lang` attribute. A keyword or script match in the
+/// text itself is trusted over the declaration; when detection is weak or
+/// fails, the declared language wins if it is supported.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text to analyze.
+/// * `declared` - An optional declared language tag (e.g., "fr-FR", "de, en").
+///
+/// # Returns
+///
+/// * `Ok(String)` - The detected or declared language code.
+/// * `Err(I18nError)` - An error if detection fails and no supported language was declared.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::detect_with_declared;
+///
+/// assert_eq!(detect_with_declared("Bonjour le monde", Some("en")).unwrap(), "fr");
+/// assert_eq!(detect_with_declared("12345", Some("de-DE")).unwrap(), "de");
+/// assert!(detect_with_declared("12345", None).is_err());
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::LanguageDetectionFailed` if the
+/// language cannot be detected and no supported language was declared, or
+/// `I18nError::UnsupportedLanguage` if strict mode rejects the detected
+/// language.
+pub fn detect_with_declared(
+    text: &str,
+    declared: Option<&str>,
+) -> Result<String, I18nError> {
+    if let Some(lang) = LANGUAGE_DETECTOR.detect_pattern(text) {
+        debug!("Detected language from patterns: {}", lang);
+        return check_detected(lang);
+    }
+
+    match declared.and_then(normalize_declared_language) {
+        Some(lang) => {
+            debug!("Using declared language: {}", lang);
+            Ok(lang)
+        }
+        None => LANGUAGE_DETECTOR.detect(text).and_then(check_detected),
+    }
+}
+
+/// Normalizes a declared language tag list to the first supported code.
+///
+/// Handles region subtags (`fr-FR`, `en_US`), comma-separated lists and
+/// quality values (`de;q=0.8`).
+fn normalize_declared_language(declared: &str) -> Option<String> {
+    declared
+        .split(',')
+        .filter_map(|tag| {
+            let tag = tag.split(';').next()?.trim();
+            let primary = tag
+                .split(|c| c == '-' || c == '_')
+                .next()?
+                .to_lowercase();
+            if is_language_supported(&primary) {
+                Some(primary)
+            } else {
+                None
+            }
+        })
+        .next()
+}
+
+/// Detects the human language of a source code comment.
+///
+/// Comment markers (`//`, `/* */`, `#`, `<!-- -->` and leading `*`) are
+/// removed, and so are code-like tokens such as `camelCase` or
+/// `snake_case` identifiers, paths, calls and numbers, leaving only the
+/// prose for detection.
+///
+/// # Arguments
+///
+/// * `comment` - A string slice that holds the comment, markers included.
+///
+/// # Returns
+///
+/// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::detect_comment_language;
+///
+/// let comment = "// Retourne la valeur de maxCount pour le cache_size";
+/// assert_eq!(detect_comment_language(comment).unwrap(), "fr");
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::LanguageDetectionFailed` if no
+/// prose remains after stripping, or its language cannot be detected, and
+/// `I18nError::UnsupportedLanguage` if strict mode rejects the detected
+/// language.
+pub fn detect_comment_language(
+    comment: &str,
+) -> Result<String, I18nError> {
+    let prose = comment_prose(comment);
+    debug!("Prose extracted from comment: {}", prose);
+    LANGUAGE_DETECTOR.detect(&prose).and_then(check_detected)
+}
+
+/// Strips comment markers and code-like tokens from a comment.
+fn comment_prose(comment: &str) -> String {
+    let text = ["/*", "*/", "<!--", "-->"]
+        .iter()
+        .fold(comment.to_string(), |text, marker| {
+            text.replace(marker, " ")
+        });
+
+    text.lines()
+        .map(|line| {
+            line.trim_start()
+                .trim_start_matches(|c| matches!(c, '/' | '#' | '*'))
+        })
+        .flat_map(str::split_whitespace)
+        .map(|token| {
+            token.trim_matches(|c: char| {
+                !c.is_alphanumeric() && c != '_'
+            })
+        })
+        .filter(|word| is_prose_word(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns `true` if a token looks like a natural-language word rather
+/// than an identifier, path or number.
+fn is_prose_word(word: &str) -> bool {
+    let mut previous_lowercase = false;
+    for c in word.chars() {
+        if !(c.is_alphabetic() || c == '\'' || c == '-') {
+            return false;
+        }
+        // An uppercase letter after a lowercase one marks camelCase.
+        if c.is_uppercase() && previous_lowercase {
+            return false;
+        }
+        previous_lowercase = c.is_lowercase();
+    }
+    !word.is_empty()
+}
+
+/// Returns a list of supported language codes.
+///
+/// # Returns
+///
+/// A vector of strings representing the supported language codes.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::supported_languages;
+///
+/// let languages = supported_languages();
+/// assert!(languages.contains(&"en".to_string()));
+/// ```
+pub fn supported_languages() -> Vec<String> {
+    vec!["en".to_string(), "fr".to_string(), "de".to_string()]
+}
+
+/// The English display name of every language langweave translates or
+/// detects, keyed by language code.
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("es", "Spanish"),
+    ("pt", "Portuguese"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("ru", "Russian"),
+    ("ar", "Arabic"),
+    ("he", "Hebrew"),
+    ("ja", "Japanese"),
+    ("zh", "Chinese"),
+    ("hi", "Hindi"),
+    ("ko", "Korean"),
+    ("id", "Indonesian"),
+];
+
+/// The name of every language in `LANGUAGE_NAMES` written in the language
+/// itself, keyed by language code.
+const LANGUAGE_AUTONYMS: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("fr", "Français"),
+    ("de", "Deutsch"),
+    ("es", "Español"),
+    ("pt", "Português"),
+    ("it", "Italiano"),
+    ("nl", "Nederlands"),
+    ("ru", "Русский"),
+    ("ar", "العربية"),
+    ("he", "עברית"),
+    ("ja", "日本語"),
+    ("zh", "中文"),
+    ("hi", "हिन्दी"),
+    ("ko", "한국어"),
+    ("id", "Bahasa Indonesia"),
+];
+
+/// Returns every language langweave translates or detects with its
+/// English display name.
+///
+/// # Returns
+///
+/// A vector of `(code, name)` pairs for all 15 languages, starting with
+/// those of `supported_languages` in the same order.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::supported_languages_named;
+///
+/// let languages = supported_languages_named();
+/// assert_eq!(languages.len(), 15);
+/// assert!(languages.contains(&("fr", "French")));
+/// assert!(languages.contains(&("nl", "Dutch")));
+/// ```
+pub fn supported_languages_named() -> Vec<(&'static str, &'static str)>
+{
+    LANGUAGE_NAMES.to_vec()
+}
+
+/// Returns the English display name of a language.
+///
+/// Names are known for the supported languages and for every language
+/// the detector can return.
+///
+/// # Arguments
+///
+/// * `code` - The language code (e.g., "en", "ja"), in any case.
+///
+/// # Returns
+///
+/// The display name, or `None` if the code is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::language_name;
+///
+/// assert_eq!(language_name("de"), Some("German"));
+/// assert_eq!(language_name("JA"), Some("Japanese"));
+/// assert_eq!(language_name("zz"), None);
+/// ```
+pub fn language_name(code: &str) -> Option<&'static str> {
+    let code = code.to_lowercase();
+    LANGUAGE_NAMES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| *name)
+}
+
+/// Returns the name of a language written in the language itself.
+///
+/// This is the name language selectors usually show, so that readers can
+/// find their own language whatever the current locale.
+///
+/// # Arguments
+///
+/// * `code` - The language code (e.g., "de", "ja"), in any case.
+///
+/// # Returns
+///
+/// The autonym, or `None` if the code is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::language_autonym;
+///
+/// assert_eq!(language_autonym("de"), Some("Deutsch"));
+/// assert_eq!(language_autonym("ja"), Some("日本語"));
+/// assert_eq!(language_autonym("zz"), None);
+/// ```
+pub fn language_autonym(code: &str) -> Option<&'static str> {
+    let code = code.to_lowercase();
+    LANGUAGE_AUTONYMS
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, autonym)| *autonym)
+}
+
+/// The direction in which a language is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right, as in English.
+    Ltr,
+    /// Right to left, as in Arabic or Hebrew.
+    Rtl,
+}
+
+impl Direction {
+    /// Returns the value of the HTML `dir` attribute for this direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::Direction;
+    ///
+    /// assert_eq!(Direction::Rtl.as_str(), "rtl");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// Returns the direction in which a language is written.
+///
+/// Arabic, Persian, Hebrew and Urdu are written right-to-left; every
+/// other language, known or not, is treated as left-to-right. Regional
+/// variants such as `ar-EG` follow their primary language.
+///
+/// # Arguments
+///
+/// * `lang` - The language code (e.g., "ar", "en"), in any case.
+///
+/// # Returns
+///
+/// The writing direction of the language.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::{text_direction, Direction};
+///
+/// assert_eq!(text_direction("ar"), Direction::Rtl);
+/// assert_eq!(text_direction("he-IL"), Direction::Rtl);
+/// assert_eq!(text_direction("en"), Direction::Ltr);
+/// ```
+pub fn text_direction(lang: &str) -> Direction {
+    if translations::is_rtl(lang) {
+        Direction::Rtl
+    } else {
+        Direction::Ltr
+    }
+}
+
+/// Detects the language of a text and returns its writing direction.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text to analyze.
+///
+/// # Returns
+///
+/// * `Result<Direction, I18nError>` - The writing direction of the detected language, or an error if detection fails.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::{detect_direction, Direction};
+///
+/// assert_eq!(detect_direction("مرحبا بالعالم").unwrap(), Direction::Rtl);
+/// assert_eq!(detect_direction("Bonjour le monde").unwrap(), Direction::Ltr);
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::LanguageDetectionFailed` if the
+/// language of the text cannot be detected, or
+/// `I18nError::UnsupportedLanguage` in strict mode if the detected
+/// language is not supported.
+pub fn detect_direction(text: &str) -> Result<Direction, I18nError> {
+    LANGUAGE_DETECTOR
+        .detect(text)
+        .and_then(check_detected)
+        .map(|lang| text_direction(&lang))
+}
+
+/// Detects the script (writing system) of a text.
+///
+/// This is lighter than language detection: every character is classified
+/// by its Unicode block, and the script with the most characters wins,
+/// ties going to the script that appears first. Digits, punctuation and
+/// symbols are ignored.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text to analyze.
+///
+/// # Returns
+///
+/// * `Result<Script, I18nError>` - The dominant script, or an error if no character belongs to a known script.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::detect_script;
+/// use langweave::script::Script;
+///
+/// assert_eq!(detect_script("שלום עולם").unwrap(), Script::Hebrew);
+/// assert_eq!(detect_script("Привет, world!").unwrap(), Script::Cyrillic);
+/// assert!(detect_script("1 + 1 = 2").is_err());
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::LanguageDetectionFailed` if no
+/// character of the text belongs to a known script.
+pub fn detect_script(text: &str) -> Result<Script, I18nError> {
+    Script::dominant(text).ok_or(I18nError::LanguageDetectionFailed)
+}
+
+/// Removes invisible format characters from text before detection or
+/// translation.
+///
+/// Byte order marks, zero-width spaces and joiners, soft hyphens, word
+/// joiners and bidirectional control characters are dropped, as they are
+/// common in text copied from the web and carry no meaning for language
+/// detection or key lookup. The text is borrowed unchanged when it
+/// contains none of them.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text to clean.
+///
+/// # Returns
+///
+/// * `Cow<str>` - The text without format characters.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::sanitize_input;
+/// use std::borrow::Cow;
+///
+/// assert_eq!(sanitize_input("\u{FEFF}Hel\u{200B}lo"), "Hello");
+/// assert!(matches!(sanitize_input("Hello"), Cow::Borrowed("Hello")));
+/// ```
+pub fn sanitize_input(text: &str) -> Cow<'_, str> {
+    if text.chars().any(is_format_char) {
+        Cow::Owned(
+            text.chars().filter(|c| !is_format_char(*c)).collect(),
+        )
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Returns `true` if the character is an invisible format character
+/// removed by `sanitize_input`.
+fn is_format_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{061C}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+    )
+}
+
+/// Validates if a given language code is supported.
+///
+/// # Arguments
+///
+/// * `lang` - The language code to validate, as a `&str`, `String` or `&String`.
+///
+/// # Returns
+///
+/// `true` if the language is supported, `false` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::is_language_supported;
+///
+/// assert!(is_language_supported("en"));
+/// assert!(!is_language_supported("zz"));
+/// ```
+pub fn is_language_supported(lang: impl AsRef<str>) -> bool {
+    supported_languages().contains(&lang.as_ref().to_lowercase())
+}
+
+thread_local! {
+    /// The ambient language set by `with_language` on the current thread.
+    static THREAD_LANGUAGE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+tokio::task_local! {
+    /// The ambient language set by `with_language_async` for the current
+    /// task.
+    static TASK_LANGUAGE: String;
+}
+
+/// Restores the previous thread-local language when dropped, even if the
+/// closure panics.
+struct LanguageGuard(Option<String>);
+
+impl Drop for LanguageGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        THREAD_LANGUAGE
+            .with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Runs a closure with an ambient language set for the current thread.
+///
+/// Inside the closure, `t` and `current_language` use `lang`. Calls can be
+/// nested; the previous language is restored when the closure returns.
+///
 /// # Arguments
 ///
-/// * `lang` - A string slice that holds the target language code (e.g., "en", "fr").
-/// * `text` - A string slice that holds the text to be translated.
+/// * `lang` - A string slice that holds the language code (e.g., "fr").
+/// * `f` - The closure to run.
 ///
 /// # Returns
 ///
-/// * `Ok(String)` - The translated text.
-/// * `Err(I18nError)` - An error if the translation fails.
+/// * `R` - The closure's result.
 ///
 /// # Examples
 ///
 /// ```
-/// use langweave::translate;
+/// use langweave::{t, with_language};
 ///
-/// let result = translate("fr", "Hello");
-/// assert_eq!(result.unwrap(), "Bonjour");
+/// let greeting = with_language("fr", || t("Hello").unwrap());
+/// assert_eq!(greeting, "Bonjour");
 /// ```
-///
-/// # Errors
-///
-/// This function will return an error if:
-/// * The specified language is not supported.
-/// * The translation process fails for any reason.
-pub fn translate(lang: &str, text: &str) -> Result<String, I18nError> {
-    if !is_language_supported(lang) {
-        return Err(I18nError::UnsupportedLanguage(lang.to_string()));
-    }
-
-    let translator = Translator::new(lang).map_err(|e| {
-        I18nError::TranslationFailed(format!(
-            "Failed to create translator: {}",
-            e
-        ))
-    })?;
-
-    // If translation fails, return the original text
-    translator.translate(text).or_else(|_| Ok(text.to_string()))
+pub fn with_language<R>(lang: &str, f: impl FnOnce() -> R) -> R {
+    let previous = THREAD_LANGUAGE
+        .with(|current| current.replace(Some(lang.to_lowercase())));
+    let _guard = LanguageGuard(previous);
+    f()
 }
 
-/// Detects the language of a given text using the composite language detector.
+/// Runs a future with an ambient language set for the current task.
+///
+/// The language follows the future across `.await` points and worker
+/// threads, and takes precedence over one set with `with_language`.
 ///
 /// # Arguments
 ///
-/// * `text` - A string slice that holds the text to analyze
+/// * `lang` - A string slice that holds the language code (e.g., "de").
+/// * `future` - The future to run.
 ///
 /// # Returns
 ///
-/// * `Result<String, I18nError>` - The detected language code if successful, or an error if detection fails
+/// * `F::Output` - The future's output.
 ///
 /// # Examples
 ///
 /// ```
-/// use langweave::language_detector::LanguageDetector;
-/// use langweave::error::I18nError;
-/// use langweave::language_detector_trait::LanguageDetectorTrait;
+/// use langweave::{t, with_language_async};
 ///
-/// async fn detect_language() -> Result<(), I18nError> {
-///     // Create a new language detector
-///     let detector = LanguageDetector::new();
-///
-///     // Detect language
-///     let lang = detector.detect_async("Hello, world!").await?;
-///     println!("Detected language: {}", lang);
-///
-///     Ok(())
+/// #[tokio::main]
+/// async fn main() {
+///     let greeting = with_language_async("de", async { t("Hello").unwrap() }).await;
+///     assert_eq!(greeting, "Hallo");
 /// }
 /// ```
-///
-/// # Errors
-///
-/// This function will return an error if:
-/// * The input text is empty or contains only non-alphabetic characters.
-/// * The language detection process fails to identify a language with sufficient confidence.
-pub async fn detect_language(text: &str) -> Result<String, I18nError> {
-    debug!("Detecting language for: {}", text);
-
-    if text.trim().is_empty() {
-        return Err(I18nError::LanguageDetectionFailed);
-    }
-
-    // Try detecting the language for the whole text first
-    if let Ok(detected_lang) =
-        LANGUAGE_DETECTOR.detect_async(text).await
-    {
-        debug!("Detected language: {}", detected_lang);
-        return Ok(detected_lang);
-    }
-
-    // Fallback: Return the first successfully detected language from word-by-word detection
-    for word in text.split_whitespace() {
-        if let Ok(detected_lang) =
-            LANGUAGE_DETECTOR.detect_async(word).await
-        {
-            debug!(
-                "Detected language from word '{}': {}",
-                word, detected_lang
-            );
-            return Ok(detected_lang);
-        }
-    }
-
-    // If no language is detected, return an error
-    Err(I18nError::LanguageDetectionFailed)
+pub async fn with_language_async<F: Future>(
+    lang: &str,
+    future: F,
+) -> F::Output {
+    TASK_LANGUAGE.scope(lang.to_lowercase(), future).await
 }
 
-/// Returns a list of supported language codes.
+/// Returns the ambient language, if one is set.
+///
+/// A language set for the current task with `with_language_async` is
+/// preferred over one set for the current thread with `with_language`.
 ///
 /// # Returns
 ///
-/// A vector of strings representing the supported language codes.
+/// * `Option<String>` - The ambient language code, or `None` outside any scope.
 ///
 /// # Examples
 ///
 /// ```
-/// use langweave::supported_languages;
+/// use langweave::{current_language, with_language};
 ///
-/// let languages = supported_languages();
-/// assert!(languages.contains(&"en".to_string()));
+/// assert_eq!(current_language(), None);
+/// with_language("fr", || assert_eq!(current_language().as_deref(), Some("fr")));
 /// ```
-pub fn supported_languages() -> Vec<String> {
-    vec!["en".to_string(), "fr".to_string(), "de".to_string()]
+pub fn current_language() -> Option<String> {
+    TASK_LANGUAGE.try_with(String::clone).ok().or_else(|| {
+        THREAD_LANGUAGE.with(|current| current.borrow().clone())
+    })
 }
 
-/// Validates if a given language code is supported.
+/// Translates a key into the ambient language.
+///
+/// This behaves like `translate` with the language set by `with_language`
+/// or `with_language_async`.
 ///
 /// # Arguments
 ///
-/// * `lang` - A string slice that holds the language code to validate.
+/// * `key` - A string slice that holds the key to be translated.
 ///
 /// # Returns
 ///
-/// `true` if the language is supported, `false` otherwise.
+/// * `Ok(String)` - The translated text.
+/// * `Err(I18nError)` - An error if no language is set or the translation fails.
 ///
 /// # Examples
 ///
 /// ```
-/// use langweave::is_language_supported;
+/// use langweave::{t, with_language};
 ///
-/// assert!(is_language_supported("en"));
-/// assert!(!is_language_supported("zz"));
+/// with_language("fr", || {
+///     assert_eq!(t("Goodbye").unwrap(), "Au revoir");
+/// });
+/// assert!(t("Goodbye").is_err());
 /// ```
-pub fn is_language_supported(lang: &str) -> bool {
-    supported_languages().contains(&lang.to_lowercase())
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnexpectedError` if no ambient
+/// language is set, and otherwise the same errors as `translate`.
+pub fn t(key: &str) -> Result<String, I18nError> {
+    let lang = current_language().ok_or_else(|| {
+        I18nError::UnexpectedError(
+            "No ambient language is set".to_string(),
+        )
+    })?;
+    translate(lang, key)
 }
 
 /// Asynchronous utilities for language processing.
@@ -244,7 +1268,7 @@ pub async fn translate_async(
                 lang.to_string(),
             ));
         }
-        let translator = Translator::new(lang).map_err(|e| {
+        let translator = Translator::cached(lang).map_err(|e| {
             I18nError::TranslationFailed(format!(
                 "Failed to create translator: {}",
                 e
@@ -272,6 +1296,168 @@ fn test_translate() {
         );
     }
 
+    #[test]
+    fn test_translate_full() {
+        translations::update_key(
+            "en",
+            "x-full-files",
+            "{user}: {n, plural, =0{no files} one{# file} other{# files}}",
+        );
+        let args = [("user", "Sam")];
+        assert_eq!(
+            translate_full("en", "x-full-files", 0, &args).unwrap(),
+            "Sam: no files"
+        );
+        assert_eq!(
+            translate_full("en", "x-full-files", 1, &args).unwrap(),
+            "Sam: 1 file"
+        );
+        assert_eq!(
+            translate_full("en", "x-full-files", 2500, &args).unwrap(),
+            "Sam: 2,500 files"
+        );
+
+        translations::register_embedded(&[(
+            "en",
+            "msgid \"x-full-day\"\nmsgid_plural \"days\"\n\
+             msgstr[0] \"{count} day\"\nmsgstr[1] \"{count} days\"\n",
+        )]);
+        assert_eq!(
+            translate_full("en", "x-full-day", 1, &[]).unwrap(),
+            "1 day"
+        );
+        assert_eq!(
+            translate_full("en", "x-full-day", 1000, &[]).unwrap(),
+            "1,000 days"
+        );
+
+        translations::update_key(
+            "en",
+            "x-full-count",
+            "{count} of {max}",
+        );
+        assert_eq!(
+            translate_full("en", "x-full-count", 4000, &[]).unwrap(),
+            "4,000 of {max}"
+        );
+        assert!(matches!(
+            translate_full("en", "x-full-missing", 1, &[]),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(matches!(
+            translate_full("zz", "Hello", 1, &[]),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_with_language() {
+        assert_eq!(current_language(), None);
+        with_language("FR", || {
+            assert_eq!(t("Hello").unwrap(), "Bonjour");
+            with_language("de", || {
+                assert_eq!(t("Hello").unwrap(), "Hallo");
+            });
+            assert_eq!(current_language().as_deref(), Some("fr"));
+        });
+        assert!(matches!(
+            t("Hello"),
+            Err(I18nError::UnexpectedError(_))
+        ));
+
+        let result = std::panic::catch_unwind(|| {
+            with_language("de", || panic!("handler failed"))
+        });
+        assert!(result.is_err());
+        assert_eq!(current_language(), None);
+    }
+
+    #[tokio::test]
+    async fn test_with_language_async() {
+        let greeting = with_language_async("fr", async {
+            tokio::task::yield_now().await;
+            with_language("de", || t("Hello").unwrap())
+        })
+        .await;
+        assert_eq!(greeting, "Bonjour");
+        assert_eq!(current_language(), None);
+    }
+
+    #[test]
+    fn test_translate_with_options() {
+        let with = |fallback| TranslateOptions { fallback };
+        assert_eq!(
+            translate_with_options(
+                "de",
+                "Hello",
+                with(FallbackMode::ReturnError)
+            )
+            .unwrap(),
+            "Hallo"
+        );
+        assert_eq!(
+            translate_with_options(
+                "de",
+                "No such key",
+                Default::default()
+            )
+            .unwrap(),
+            "No such key"
+        );
+        assert_eq!(
+            translate_with_options(
+                "de",
+                "Nokey",
+                with(FallbackMode::ReturnEmpty)
+            )
+            .unwrap(),
+            ""
+        );
+        assert!(matches!(
+            translate_with_options(
+                "de",
+                "Nokey",
+                with(FallbackMode::ReturnError)
+            ),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(matches!(
+            translate_with_options(
+                "zz",
+                "Hello",
+                with(FallbackMode::ReturnOriginal)
+            ),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_owned_arguments() {
+        let (lang, key) = ("fr".to_string(), "Hello".to_string());
+        assert_eq!(translate(&lang, &key).unwrap(), "Bonjour");
+        assert_eq!(translate(lang.clone(), key).unwrap(), "Bonjour");
+        assert!(is_language_supported(&lang));
+        assert!(is_language_supported(lang));
+        assert!(!is_language_supported(String::from("zz")));
+    }
+
+    #[test]
+    fn test_translate_checked() {
+        assert_eq!(translate_checked("de", "Hello").unwrap(), "Hallo");
+        assert_eq!(
+            translate("de", "Missing key").unwrap(),
+            "Missing key"
+        );
+        assert!(matches!(
+            translate_checked("de", "Missing key"),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(matches!(
+            translate_checked("zz", "Hello"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
     #[test]
     fn test_translate_error() {
         assert!(matches!(
@@ -348,6 +1534,110 @@ async fn test_detect_language_fallback() {
         );
     }
 
+    #[test]
+    fn test_localize_paragraphs() {
+        let results = localize_paragraphs(
+            "de",
+            &["Goodbye", "Hallo Welt", "The weather is nice", "42"],
+        );
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].0, "en");
+        assert_eq!(results[0].1.as_deref().unwrap(), "Auf Wiedersehen");
+        assert_eq!(results[1].0, "de");
+        assert_eq!(results[1].1.as_deref().unwrap(), "Hallo Welt");
+        assert!(matches!(
+            results[2].1,
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert_eq!(results[3].0, "und");
+        assert!(matches!(
+            results[3].1,
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(decode_text(b"\xEF\xBB\xBFHello"), "Hello");
+        assert_eq!(decode_text(&[0xFF, 0xFE, b'H', 0, b'i', 0]), "Hi");
+        assert_eq!(decode_text(&[0xFE, 0xFF, 0, b'H', 0, b'i']), "Hi");
+        assert_eq!(decode_text(b"plain"), "plain");
+    }
+
+    #[tokio::test]
+    async fn test_detect_language_bytes() {
+        assert_eq!(
+            detect_language_bytes(b"\xEF\xBB\xBFDer schnelle Fuchs")
+                .await
+                .unwrap(),
+            "de"
+        );
+        assert!(matches!(
+            detect_language_bytes(b"\xEF\xBB\xBF").await,
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_detect_with_declared() {
+        assert_eq!(
+            detect_with_declared("Der schnelle Fuchs", Some("fr"))
+                .unwrap(),
+            "de"
+        );
+        assert_eq!(
+            detect_with_declared("🎉", Some("FR-ca")).unwrap(),
+            "fr"
+        );
+        assert_eq!(
+            detect_with_declared("!!!", Some("xx, en_GB;q=0.8"))
+                .unwrap(),
+            "en"
+        );
+        assert!(matches!(
+            detect_with_declared("!!!", Some("xx")),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_comment_prose() {
+        assert_eq!(
+            comment_prose("/* Returns the userId of self.owner() */"),
+            "Returns the of"
+        );
+        assert_eq!(
+            comment_prose("<!-- Die Seite -->\n# max_len ist 42"),
+            "Die Seite ist"
+        );
+        assert_eq!(
+            comment_prose("/// l'appel\n * HTTP-Anfrage"),
+            "l'appel HTTP-Anfrage"
+        );
+    }
+
+    #[test]
+    fn test_detect_comment_language() {
+        assert_eq!(
+            detect_comment_language(
+                "// Berechnet den Wert von userId für die calculate_total()"
+            )
+            .unwrap(),
+            "de"
+        );
+        assert_eq!(
+            detect_comment_language(
+                "# The config_path is read by loadConfig"
+            )
+            .unwrap(),
+            "en"
+        );
+        assert!(matches!(
+            detect_comment_language("// TODO_1: fooBar(baz_qux)"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
     #[test]
     fn test_supported_languages() {
         let languages = supported_languages();
@@ -356,6 +1646,127 @@ fn test_supported_languages() {
         assert!(languages.contains(&"de".to_string()));
     }
 
+    #[test]
+    fn test_supported_languages_named() {
+        let named = supported_languages_named();
+        assert_eq!(named.len(), 15);
+        for (code, (named, _)) in
+            supported_languages().iter().zip(&named)
+        {
+            assert_eq!(code, named);
+        }
+        assert!(named.contains(&("it", "Italian")));
+        assert!(named.contains(&("id", "Indonesian")));
+        for (lang, _) in LanguageDetector::pattern_sources() {
+            assert!(
+                language_name(&lang).is_some(),
+                "{} has no name",
+                lang
+            );
+        }
+    }
+
+    #[test]
+    fn test_language_name() {
+        assert_eq!(language_name("fr"), Some("French"));
+        assert_eq!(language_name("Ko"), Some("Korean"));
+        assert_eq!(language_name(""), None);
+        for (code, name) in LANGUAGE_NAMES {
+            assert!(name.is_ascii(), "{} has a non-ASCII name", code);
+        }
+    }
+
+    #[test]
+    fn test_language_autonym() {
+        assert_eq!(language_autonym("FR"), Some("Français"));
+        assert_eq!(language_autonym("ru"), Some("Русский"));
+        assert_eq!(language_autonym("xx"), None);
+        assert_eq!(language_autonym("nl"), Some("Nederlands"));
+        for ((code, _), (named, _)) in
+            LANGUAGE_AUTONYMS.iter().zip(LANGUAGE_NAMES)
+        {
+            assert_eq!(code, named);
+        }
+        assert_eq!(LANGUAGE_AUTONYMS.len(), LANGUAGE_NAMES.len());
+    }
+
+    #[test]
+    fn test_text_direction() {
+        for lang in ["ar", "AR", "fa", "he", "ur-PK"] {
+            assert_eq!(text_direction(lang), Direction::Rtl);
+        }
+        for lang in ["en", "fr-CA", "ja", "", "zz"] {
+            assert_eq!(text_direction(lang), Direction::Ltr);
+        }
+        assert_eq!(Direction::Ltr.as_str(), "ltr");
+    }
+
+    #[test]
+    fn test_detect_direction() {
+        assert_eq!(
+            detect_direction("السلام عليكم").unwrap(),
+            Direction::Rtl
+        );
+        assert_eq!(
+            detect_direction("Der Hund und die Katze").unwrap(),
+            Direction::Ltr
+        );
+        assert!(matches!(
+            detect_direction("12345"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_input() {
+        assert_eq!(
+            sanitize_input("\u{FEFF}Bon\u{200B}jour\u{200D}\u{2060}"),
+            "Bonjour"
+        );
+        assert_eq!(sanitize_input("\u{202B}שלום\u{202C}"), "שלום");
+        assert_eq!(sanitize_input("co\u{00AD}operate"), "cooperate");
+        assert!(matches!(
+            sanitize_input("Hello, world!\n"),
+            Cow::Borrowed("Hello, world!\n")
+        ));
+
+        assert_eq!(
+            LANGUAGE_DETECTOR.detect("\u{200B}Bonjour").unwrap(),
+            "fr"
+        );
+        let translator = Translator::new("fr").unwrap();
+        assert_eq!(
+            translator.translate("\u{FEFF}Hel\u{200C}lo").unwrap(),
+            "Bonjour"
+        );
+    }
+
+    #[test]
+    fn test_detect_script() {
+        let cases = [
+            ("Hello", Script::Latin),
+            ("مرحبا", Script::Arabic),
+            ("नमस्ते", Script::Devanagari),
+            ("漢字", Script::Han),
+            ("ひらがな", Script::Hiragana),
+            ("カタカナ", Script::Katakana),
+            ("안녕하세요", Script::Hangul),
+            ("abc שלום עולם", Script::Hebrew),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(
+                detect_script(text).unwrap(),
+                expected,
+                "{}",
+                text
+            );
+        }
+        assert!(matches!(
+            detect_script(""),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+    }
+
     #[test]
     fn test_is_language_supported() {
         assert!(is_language_supported("en"));
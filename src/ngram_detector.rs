@@ -0,0 +1,264 @@
+// Copyright © 2024 LangWeave. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # N-gram Language Detector
+//!
+//! This module provides an `NgramDetector` that classifies text by
+//! comparing its character tri-grams with per-language frequency profiles.
+//!
+//! Profiles are built from the loaded translations, so every language
+//! with a catalog can be detected, including languages the built-in
+//! keyword patterns of `LanguageDetector` do not cover. Extra sample text
+//! can be added with `NgramDetector::with_sample`. The detector is meant
+//! to be composed with others in a `CompositeLanguageDetector`.
+//!
+//! ## Examples
+//!
+//! ```
+//! use langweave::language_detector::LanguageDetector;
+//! use langweave::language_detector_trait::CompositeLanguageDetector;
+//! use langweave::ngram_detector::NgramDetector;
+//!
+//! let mut composite = CompositeLanguageDetector::new();
+//! composite.add_detector(Box::new(LanguageDetector::new()));
+//! composite.add_detector(Box::new(
+//!     NgramDetector::new().with_sample("it", "ciao grazie mille buongiorno"),
+//! ));
+//!
+//! assert_eq!(composite.detect("grazie mille").unwrap(), "it");
+//! ```
+
+use crate::error::I18nError;
+use crate::language_detector_trait::LanguageDetectorTrait;
+use crate::strict_rejects;
+use crate::translations;
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// A character tri-gram frequency profile.
+#[derive(Debug, Clone, Default)]
+struct Profile {
+    counts: HashMap<String, f64>,
+    norm: f64,
+}
+
+impl Profile {
+    /// Builds the profile of a text.
+    fn of(text: &str) -> Self {
+        let mut profile = Profile::default();
+        profile.add(text);
+        profile
+    }
+
+    /// Adds the tri-grams of a text to the profile.
+    ///
+    /// Text is lowercased and split into words of letters, each padded
+    /// with a space on both sides so that word boundaries are captured.
+    fn add(&mut self, text: &str) {
+        for word in text
+            .split(|c: char| !c.is_alphabetic())
+            .filter(|word| !word.is_empty())
+        {
+            let padded: Vec<char> =
+                format!(" {} ", word.to_lowercase()).chars().collect();
+            for trigram in padded.windows(3) {
+                *self
+                    .counts
+                    .entry(trigram.iter().collect())
+                    .or_insert(0.0) += 1.0;
+            }
+        }
+        self.norm =
+            self.counts.values().map(|n| n * n).sum::<f64>().sqrt();
+    }
+
+    /// Returns the cosine similarity between two profiles, between `0.0`
+    /// and `1.0`.
+    fn similarity(&self, other: &Profile) -> f64 {
+        if self.norm == 0.0 || other.norm == 0.0 {
+            return 0.0;
+        }
+        let dot: f64 = self
+            .counts
+            .iter()
+            .filter_map(|(trigram, n)| {
+                other.counts.get(trigram).map(|m| n * m)
+            })
+            .sum();
+        dot / (self.norm * other.norm)
+    }
+}
+
+/// A detector that classifies text by the cosine similarity of its
+/// character tri-grams to per-language profiles.
+#[derive(Debug, Clone, Default)]
+pub struct NgramDetector {
+    profiles: Vec<(String, Profile)>,
+}
+
+impl NgramDetector {
+    /// Creates a new `NgramDetector` with a profile for every language
+    /// whose translations are loaded.
+    ///
+    /// # Returns
+    ///
+    /// * `NgramDetector` - A detector built from the translation catalogs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::ngram_detector::NgramDetector;
+    ///
+    /// let detector = NgramDetector::new();
+    /// assert!(detector.languages().contains(&"fr".to_string()));
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        translations::corpora()
+            .into_iter()
+            .fold(NgramDetector::default(), |detector, (lang, text)| {
+                detector.with_sample(&lang, &text)
+            })
+    }
+
+    /// Adds sample text to the profile of a language, creating the
+    /// profile if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - A string slice that holds the language code of the sample (e.g., "it").
+    /// * `sample` - A string slice that holds text written in the language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::language_detector_trait::LanguageDetectorTrait;
+    /// use langweave::ngram_detector::NgramDetector;
+    ///
+    /// let detector = NgramDetector::default()
+    ///     .with_sample("it", "il gatto nero dorme sul divano")
+    ///     .with_sample("nl", "de zwarte kat slaapt op de bank");
+    /// assert_eq!(detector.detect("il gatto").unwrap(), "it");
+    /// assert_eq!(detector.detect("de kat").unwrap(), "nl");
+    /// ```
+    #[must_use]
+    pub fn with_sample(mut self, lang: &str, sample: &str) -> Self {
+        let lang = lang.to_lowercase();
+        match self.profiles.iter_mut().find(|(l, _)| *l == lang) {
+            Some((_, profile)) => profile.add(sample),
+            None => self.profiles.push((lang, Profile::of(sample))),
+        }
+        self
+    }
+
+    /// Returns the codes of the languages the detector has profiles for.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The language codes, in the order their profiles were added.
+    pub fn languages(&self) -> Vec<String> {
+        self.profiles.iter().map(|(lang, _)| lang.clone()).collect()
+    }
+
+    /// Returns the language whose profile is most similar to the text,
+    /// along with the cosine similarity.
+    ///
+    /// Ties go to the profile added first.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(String, f64)>` - The language code and a similarity between `0.0` and `1.0`, or `None` if the text shares no tri-gram with any profile.
+    pub fn detect_with_score(
+        &self,
+        text: &str,
+    ) -> Option<(String, f64)> {
+        let profile = Profile::of(text);
+        self.profiles
+            .iter()
+            .filter(|(lang, _)| !strict_rejects(lang))
+            .map(|(lang, other)| (lang, profile.similarity(other)))
+            .filter(|(_, score)| *score > 0.0)
+            .fold(
+                None,
+                |best: Option<(&String, f64)>, (lang, score)| match best
+                {
+                    Some((_, most)) if most >= score => best,
+                    _ => Some((lang, score)),
+                },
+            )
+            .map(|(lang, score)| (lang.clone(), score))
+    }
+}
+
+#[async_trait]
+impl LanguageDetectorTrait for NgramDetector {
+    /// Detects the language of the given text from its tri-grams.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed`
+    /// if the text shares no tri-gram with any language profile.
+    fn detect(&self, text: &str) -> Result<String, I18nError> {
+        self.detect_with_score(text)
+            .map(|(lang, _)| lang)
+            .ok_or(I18nError::LanguageDetectionFailed)
+    }
+
+    /// Detects the language of the given text from its tri-grams
+    /// asynchronously.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `I18nError::LanguageDetectionFailed`
+    /// if the text shares no tri-gram with any language profile.
+    async fn detect_async(
+        &self,
+        text: &str,
+    ) -> Result<String, I18nError> {
+        self.detect(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_from_translations() {
+        let detector = NgramDetector::new();
+        let languages = detector.languages();
+        for lang in ["de", "en", "fr"] {
+            assert!(languages.contains(&lang.to_string()));
+        }
+
+        assert_eq!(detector.detect("Au revoir, merci").unwrap(), "fr");
+        assert_eq!(detector.detect("Auf Wiedersehen").unwrap(), "de");
+        assert_eq!(
+            detector.detect("Goodbye, thank you").unwrap(),
+            "en"
+        );
+    }
+
+    #[test]
+    fn test_similarity_and_failure() {
+        let detector = NgramDetector::default()
+            .with_sample("it", "buongiorno")
+            .with_sample("IT", "grazie");
+        assert_eq!(detector.languages(), vec!["it".to_string()]);
+
+        let (lang, score) =
+            detector.detect_with_score("grazie").unwrap();
+        assert_eq!(lang, "it");
+        assert!(score > 0.0 && score <= 1.0);
+
+        assert!(matches!(
+            detector.detect("xyz 123"),
+            Err(I18nError::LanguageDetectionFailed)
+        ));
+        assert!(NgramDetector::default().detect("grazie").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright © 2024 LangWeave. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Script Module
+//!
+//! This module defines the `Script` enum describing the writing systems
+//! langweave works with, independently of any particular language.
+
+use std::fmt;
+
+/// A writing system (Unicode script) used to write text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Script {
+    /// The Latin script (e.g., English, French, German).
+    Latin,
+    /// The Cyrillic script (e.g., Russian).
+    Cyrillic,
+    /// The Arabic script.
+    Arabic,
+    /// The Hebrew script.
+    Hebrew,
+    /// The Devanagari script (e.g., Hindi).
+    Devanagari,
+    /// Han ideographs (e.g., Chinese, Japanese kanji).
+    Han,
+    /// The Hiragana syllabary (Japanese).
+    Hiragana,
+    /// The Katakana syllabary (Japanese).
+    Katakana,
+    /// The Hangul alphabet (Korean).
+    Hangul,
+}
+
+impl Script {
+    /// Returns the script a character belongs to, if it is one of the
+    /// scripts described by this enum.
+    ///
+    /// Digits, punctuation, symbols and whitespace belong to no script.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The character to classify.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Script>` - The character's script, or `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::script::Script;
+    ///
+    /// assert_eq!(Script::of('é'), Some(Script::Latin));
+    /// assert_eq!(Script::of('ж'), Some(Script::Cyrillic));
+    /// assert_eq!(Script::of('7'), None);
+    /// ```
+    pub fn of(c: char) -> Option<Script> {
+        let script = match c {
+            'A'..='Z' | 'a'..='z' => Script::Latin,
+            '\u{00D7}' | '\u{00F7}' => return None,
+            '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' => {
+                Script::Latin
+            }
+            '\u{0400}'..='\u{052F}' => Script::Cyrillic,
+            '\u{0591}'..='\u{05F4}' | '\u{FB1D}'..='\u{FB4F}' => {
+                Script::Hebrew
+            }
+            '\u{0600}'..='\u{06FF}'
+            | '\u{0750}'..='\u{077F}'
+            | '\u{08A0}'..='\u{08FF}'
+            | '\u{FB50}'..='\u{FDFF}'
+            | '\u{FE70}'..='\u{FEFE}' => Script::Arabic,
+            '\u{0900}'..='\u{097F}' => Script::Devanagari,
+            '\u{3040}'..='\u{309F}' => Script::Hiragana,
+            '\u{30A0}'..='\u{30FF}'
+            | '\u{31F0}'..='\u{31FF}'
+            | '\u{FF66}'..='\u{FF9F}' => Script::Katakana,
+            '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FFFF}' => Script::Han,
+            '\u{1100}'..='\u{11FF}'
+            | '\u{3130}'..='\u{318F}'
+            | '\u{AC00}'..='\u{D7AF}' => Script::Hangul,
+            _ => return None,
+        };
+        Some(script)
+    }
+
+    /// Returns the most frequent script among the characters of `text`.
+    ///
+    /// Ties go to the script that appears first.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice that holds the text to analyze.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Script>` - The dominant script, or `None` if no character has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::script::Script;
+    ///
+    /// assert_eq!(Script::dominant("Привет, world!"), Some(Script::Cyrillic));
+    /// assert_eq!(Script::dominant("1234"), None);
+    /// ```
+    pub fn dominant(text: &str) -> Option<Script> {
+        let mut counts: Vec<(Script, usize)> = Vec::new();
+        for script in text.chars().filter_map(Script::of) {
+            match counts.iter_mut().find(|(s, _)| *s == script) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((script, 1)),
+            }
+        }
+        counts
+            .into_iter()
+            .fold(None, |best: Option<(Script, usize)>, (s, count)| {
+                match best {
+                    Some((_, most)) if most >= count => best,
+                    _ => Some((s, count)),
+                }
+            })
+            .map(|(script, _)| script)
+    }
+
+    /// Returns the English name of the script.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::script::Script;
+    ///
+    /// assert_eq!(Script::Cyrillic.name(), "Cyrillic");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Script::Latin => "Latin",
+            Script::Cyrillic => "Cyrillic",
+            Script::Arabic => "Arabic",
+            Script::Hebrew => "Hebrew",
+            Script::Devanagari => "Devanagari",
+            Script::Han => "Han",
+            Script::Hiragana => "Hiragana",
+            Script::Katakana => "Katakana",
+            Script::Hangul => "Hangul",
+        }
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_of() {
+        let cases = [
+            ('a', Some(Script::Latin)),
+            ('ß', Some(Script::Latin)),
+            ('×', None),
+            ('я', Some(Script::Cyrillic)),
+            ('م', Some(Script::Arabic)),
+            ('ש', Some(Script::Hebrew)),
+            ('\u{FB4F}', Some(Script::Hebrew)),
+            ('\u{FB50}', Some(Script::Arabic)),
+            ('\u{FEFF}', None),
+            ('न', Some(Script::Devanagari)),
+            ('あ', Some(Script::Hiragana)),
+            ('カ', Some(Script::Katakana)),
+            ('漢', Some(Script::Han)),
+            ('한', Some(Script::Hangul)),
+            ('!', None),
+        ];
+        for (c, expected) in cases {
+            assert_eq!(Script::of(c), expected, "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn test_dominant() {
+        assert_eq!(Script::dominant("ab где"), Some(Script::Cyrillic));
+        // Ties go to the script seen first.
+        assert_eq!(Script::dominant("abc где"), Some(Script::Latin));
+        assert_eq!(Script::dominant(""), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Script::Latin.to_string(), "Latin");
+        assert_eq!(format!("{}", Script::Hangul), "Hangul");
+    }
+}
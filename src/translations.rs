@@ -1,122 +1,2394 @@
-use crate::I18nError;
-use lazy_static::lazy_static;
-use std::collections::HashMap;
+use crate::language_detector::LanguageDetector;
+use crate::language_detector_trait::LanguageDetectorTrait;
+use crate::translator;
+use crate::{is_strict, strict_rejects, I18nError};
+use async_trait::async_trait;
+use log::{debug, warn};
+use once_cell::sync::{Lazy, OnceCell};
+use regex::Regex;
+#[cfg(feature = "snapshot")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::env;
+use std::fmt;
 use std::fs;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::mem::{self, size_of};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{
+    Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
 
-type TranslationMap = HashMap<String, HashMap<String, String>>;
+type TranslationMap = HashMap<String, Catalog>;
 
-lazy_static! {
-    static ref TRANSLATIONS: TranslationMap = load_all_translations();
+/// The translations loaded for one language.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "snapshot", derive(Serialize, Deserialize))]
+struct Catalog {
+    /// Translations keyed by message ID.
+    messages: HashMap<String, String>,
+    /// The `msgstr[n]` forms of plural entries, keyed by message ID.
+    plurals: HashMap<String, Vec<String>>,
 }
 
-fn load_translations_from_dir(dir: &Path) -> TranslationMap {
+impl Catalog {
+    /// Returns `true` if the catalog holds no translations.
+    fn is_empty(&self) -> bool {
+        self.messages.is_empty() && self.plurals.is_empty()
+    }
+
+    /// Merges another catalog into this one, overriding shared keys.
+    fn extend(&mut self, other: Catalog) {
+        self.messages.extend(other.messages);
+        self.plurals.extend(other.plurals);
+    }
+}
+
+/// Language codes written right-to-left.
+const RTL_LANGUAGES: &[&str] = &["ar", "fa", "he", "ur"];
+
+/// Typical length of translated UI text relative to English, per language.
+const EXPANSION_FACTORS: &[(&str, f64)] = &[
+    ("en", 1.0),
+    ("de", 1.35),
+    ("fr", 1.2),
+    ("es", 1.25),
+    ("pt", 1.25),
+    ("it", 1.2),
+    ("nl", 1.3),
+    ("ru", 1.15),
+    ("ar", 1.25),
+    ("hi", 1.2),
+    ("ja", 0.55),
+    ("ko", 0.7),
+    ("zh", 0.5),
+];
+
+/// Unicode RIGHT-TO-LEFT EMBEDDING control character.
+const RLE: char = '\u{202B}';
+
+/// Unicode POP DIRECTIONAL FORMATTING control character.
+const PDF: char = '\u{202C}';
+
+/// The translation store, loaded from the locales directory on first use
+/// unless embedded catalogs were registered beforehand.
+static TRANSLATIONS: OnceCell<RwLock<TranslationMap>> = OnceCell::new();
+
+/// The directory set with `set_locales_dir`, if any.
+static LOCALES_DIR: Lazy<RwLock<Option<PathBuf>>> =
+    Lazy::new(|| RwLock::new(None));
+
+/// The errors reported while loading catalogs, see `load_errors`.
+static LOAD_ERRORS: Lazy<RwLock<Vec<I18nError>>> =
+    Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Incremented every time the translation store is modified in place.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Embeds `.po` catalogs into the binary at compile time.
+///
+/// The first argument is the catalog directory, relative to the invoking
+/// crate's `Cargo.toml`, and the remaining arguments are the language codes
+/// to embed; `"fr"` reads `<dir>/fr.po`. The catalogs are registered with
+/// the translation store when the macro runs. If this happens before any
+/// translation is looked up, the store never reads the filesystem, which
+/// suits single-binary deployments.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate;
+///
+/// langweave::embed_translations!("locales/", "en", "fr", "de");
+/// assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+/// ```
+#[macro_export]
+macro_rules! embed_translations {
+    ($dir:literal, $($lang:literal),+ $(,)?) => {
+        $crate::translations::register_embedded(&[
+            $((
+                $lang,
+                include_str!(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/",
+                    $dir,
+                    "/",
+                    $lang,
+                    ".po"
+                )),
+            )),+
+        ])
+    };
+}
+
+/// Registers catalogs given as `(language code, PO source)` pairs.
+///
+/// This is the runtime half of `embed_translations!`, and can also be used
+/// directly with catalogs obtained elsewhere (for example generated by a
+/// build script). If the store has not been loaded yet, it is initialised
+/// from these catalogs alone and the `locales/` directory is never read;
+/// otherwise their entries are merged into the loaded store.
+///
+/// # Arguments
+///
+/// * `catalogs` - The `(language code, PO source)` pairs to register.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{register_embedded, translate};
+///
+/// register_embedded(&[("fr", "msgid \"Cat\"\nmsgstr \"Chat\"\n")]);
+/// assert_eq!(translate("fr", "Cat").unwrap(), "Chat");
+/// ```
+pub fn register_embedded(catalogs: &[(&str, &str)]) {
+    let mut embedded = TranslationMap::new();
+    for (lang, source) in catalogs {
+        match parse_po(source.as_bytes()) {
+            Ok(translations) => embedded
+                .entry(lang.to_lowercase())
+                .or_default()
+                .extend(translations),
+            Err(e) => {
+                warn!(
+                    "Error loading embedded catalog for {}: {}",
+                    lang, e
+                );
+                let error = I18nError::load_failed_with_source(
+                    format!("embedded catalog for {}", lang),
+                    e,
+                );
+                LOAD_ERRORS
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(error);
+            }
+        }
+    }
+
+    let mut pending = Some(embedded);
+    let store = TRANSLATIONS.get_or_init(|| {
+        RwLock::new(pending.take().unwrap_or_default())
+    });
+    if let Some(embedded) = pending {
+        let mut store =
+            store.write().unwrap_or_else(PoisonError::into_inner);
+        for (lang, translations) in embedded {
+            store.entry(lang).or_default().extend(translations);
+        }
+        bump_generation();
+    }
+}
+
+/// Sets the translation of a single key, creating the language if needed.
+///
+/// The entry is replaced atomically: concurrent lookups see either the old
+/// or the new value. The store generation is incremented so that caches
+/// built on top of the catalog can be invalidated.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to update.
+/// * `value` - The new translation of the key.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{generation, translate, update_key};
+///
+/// let before = generation();
+/// update_key("fr", "Dog", "Chien");
+/// assert_eq!(translate("fr", "Dog").unwrap(), "Chien");
+/// assert!(generation() > before);
+/// ```
+pub fn update_key(lang: &str, key: &str, value: &str) {
+    let _ = write_store()
+        .entry(lang.to_lowercase())
+        .or_default()
+        .messages
+        .insert(key.to_string(), value.to_string());
+    bump_generation();
+}
+
+/// Removes the translation of a single key.
+///
+/// Plural forms loaded for the key are removed as well. The store
+/// generation is incremented only if an entry was removed.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to remove.
+///
+/// # Returns
+///
+/// * `bool` - `true` if the key had a translation that was removed.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{remove_key, translate, update_key};
+///
+/// update_key("de", "Hund", "Hund");
+/// assert!(remove_key("de", "Hund"));
+/// assert!(translate("de", "Hund").is_err());
+/// assert!(!remove_key("de", "Hund"));
+/// ```
+pub fn remove_key(lang: &str, key: &str) -> bool {
+    let removed = write_store()
+        .get_mut(lang.to_lowercase().as_str())
+        .map_or(false, |catalog| {
+            let plural = catalog.plurals.remove(key).is_some();
+            catalog.messages.remove(key).is_some() || plural
+        });
+    if removed {
+        bump_generation();
+    }
+    removed
+}
+
+/// Returns the current generation of the translation store.
+///
+/// The generation starts at zero and increases whenever the store is
+/// modified in place, so a cache can record it and compare later to detect
+/// stale entries.
+///
+/// # Returns
+///
+/// * `u64` - The current store generation.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{generation, update_key};
+///
+/// let before = generation();
+/// update_key("en", "Bird", "Bird");
+/// assert_ne!(generation(), before);
+/// ```
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+/// Estimates the memory held by the translation store, in bytes.
+///
+/// The estimate sums the allocated capacity of every language code, key,
+/// value and plural form string, plus the bucket storage of each
+/// language's maps. It
+/// ignores allocator overhead, so the true footprint is somewhat higher.
+///
+/// # Returns
+///
+/// * `usize` - The estimated number of bytes held by loaded translations.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{memory_usage, update_key};
+///
+/// let before = memory_usage();
+/// update_key("en", "Legal notice", &"x".repeat(4096));
+/// assert!(memory_usage() >= before + 4096);
+/// ```
+pub fn memory_usage() -> usize {
+    let store = read_store();
+    let entry_size = size_of::<(String, String)>() + 1;
+    let plural_size = size_of::<(String, Vec<String>)>() + 1;
+    let map_size = |capacity: usize, entry_size: usize| {
+        size_of::<HashMap<String, String>>() + capacity * entry_size
+    };
+
+    map_size(store.capacity(), size_of::<(String, Catalog)>() + 1)
+        + store
+            .iter()
+            .map(|(lang, catalog)| {
+                lang.capacity()
+                    + map_size(catalog.messages.capacity(), entry_size)
+                    + map_size(catalog.plurals.capacity(), plural_size)
+                    + catalog
+                        .messages
+                        .iter()
+                        .map(|(key, value)| {
+                            key.capacity() + value.capacity()
+                        })
+                        .sum::<usize>()
+                    + catalog
+                        .plurals
+                        .iter()
+                        .map(|(key, forms)| {
+                            key.capacity()
+                                + forms.capacity() * size_of::<String>()
+                                + forms
+                                    .iter()
+                                    .map(String::capacity)
+                                    .sum::<usize>()
+                        })
+                        .sum::<usize>()
+            })
+            .sum::<usize>()
+}
+
+/// Removes every translation from the store.
+///
+/// Intended for tests that need to start from a known, empty state. The
+/// store generation is incremented.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{clear, translate, update_key};
+///
+/// clear();
+/// assert!(translate("fr", "Hello").is_err());
+/// update_key("fr", "Hello", "Salut");
+/// assert_eq!(translate("fr", "Hello").unwrap(), "Salut");
+/// ```
+#[cfg(feature = "test-util")]
+pub fn clear() {
+    write_store().clear();
+    store_replaced();
+}
+
+/// Replaces the store with the catalogs in the locales directory,
+/// discarding any registered or updated entries.
+///
+/// Intended for tests that need to start from the on-disk catalogs. The
+/// store generation is incremented.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{reset_to_filesystem, translate, update_key};
+///
+/// update_key("fr", "Hello", "Salut");
+/// reset_to_filesystem();
+/// assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+/// ```
+#[cfg(feature = "test-util")]
+pub fn reset_to_filesystem() {
+    *write_store() = load_all_translations();
+    store_replaced();
+}
+
+/// Serializes the whole translation store into a compact binary snapshot.
+///
+/// The snapshot can be baked at build time and restored with
+/// `load_snapshot` on startup, so `.po` files never need to be parsed in
+/// production.
+///
+/// # Returns
+///
+/// * `Vec<u8>` - The encoded snapshot.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{load_snapshot, snapshot, translate};
+///
+/// let bytes = snapshot();
+/// load_snapshot(&bytes).unwrap();
+/// assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+/// ```
+#[cfg(feature = "snapshot")]
+pub fn snapshot() -> Vec<u8> {
+    bincode::serialize(&*read_store())
+        .expect("Failed to serialize the translation store")
+}
+
+/// Replaces the translation store with the contents of a snapshot.
+///
+/// If the store has not been loaded yet, the `locales/` directory is never
+/// read. The store generation is incremented.
+///
+/// # Arguments
+///
+/// * `bytes` - A snapshot produced by `snapshot`.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the snapshot was restored.
+/// * `Err(I18nError)` - An error if the snapshot cannot be decoded.
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnexpectedError` if `bytes` is
+/// not a valid snapshot; the store is left unchanged.
+#[cfg(feature = "snapshot")]
+pub fn load_snapshot(bytes: &[u8]) -> Result<(), I18nError> {
+    let restored: TranslationMap = bincode::deserialize(bytes)
+        .map_err(|e| {
+            I18nError::UnexpectedError(format!(
+                "Invalid translation snapshot: {}",
+                e
+            ))
+        })?;
+
+    replace_store(restored);
+    Ok(())
+}
+
+/// Loads the translation store from the locales directory, failing if
+/// any catalog cannot be loaded.
+///
+/// The store is otherwise loaded lazily on first use, skipping catalogs
+/// that fail to load. Call this at startup to fail fast when translation
+/// assets are missing or broken. On success, the store is replaced and
+/// its generation incremented; on failure, it is left unchanged.
+///
+/// # Returns
+///
+/// * `Ok(())` - If every catalog was loaded.
+/// * `Err(I18nError)` - The first error encountered.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{init, translate};
+///
+/// init().unwrap();
+/// assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::LoadFailed` if the locales
+/// directory does not exist or one of its catalogs cannot be read or
+/// parsed. The errors of all failed catalogs are available from
+/// `load_errors`.
+pub fn init() -> Result<(), I18nError> {
+    let (translations, errors) = read_locales_dir()?;
+    let failed = errors.first().cloned();
+    *LOAD_ERRORS.write().unwrap_or_else(PoisonError::into_inner) =
+        errors;
+    match failed {
+        Some(error) => Err(error),
+        None => {
+            replace_store(translations);
+            Ok(())
+        }
+    }
+}
+
+/// Replaces the whole translation store, initializing it if needed.
+fn replace_store(translations: TranslationMap) {
+    let mut pending = Some(translations);
+    let store = TRANSLATIONS.get_or_init(|| {
+        RwLock::new(pending.take().unwrap_or_default())
+    });
+    if let Some(translations) = pending {
+        *store.write().unwrap_or_else(PoisonError::into_inner) =
+            translations;
+    }
+    store_replaced();
+}
+
+/// Sets the directory translation catalogs are loaded from.
+///
+/// By default catalogs are loaded from `locales/` in the current working
+/// directory, which breaks when the binary runs elsewhere. Call this at
+/// startup with an absolute path to load from a fixed location instead.
+/// If the store was already loaded, it is reloaded from the new directory,
+/// discarding registered or updated entries, and the store generation is
+/// incremented.
+///
+/// # Arguments
+///
+/// * `path` - The directory holding the catalogs.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{set_locales_dir, translate};
+///
+/// set_locales_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/locales"));
+/// assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+/// ```
+pub fn set_locales_dir(path: impl Into<PathBuf>) {
+    *LOCALES_DIR.write().unwrap_or_else(PoisonError::into_inner) =
+        Some(path.into());
+    if let Some(store) = TRANSLATIONS.get() {
+        *store.write().unwrap_or_else(PoisonError::into_inner) =
+            load_all_translations();
+        store_replaced();
+    }
+}
+
+/// Returns the errors reported while loading catalogs.
+///
+/// Catalogs that cannot be read or parsed are skipped rather than failing
+/// the whole load; the reason for each is collected here. The list is
+/// replaced every time the locales directory is (re)loaded, and errors in
+/// catalogs passed to `register_embedded` are appended to it.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{load_errors, translate};
+///
+/// assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+/// assert!(load_errors().is_empty());
+/// ```
+pub fn load_errors() -> Vec<I18nError> {
+    LOAD_ERRORS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Returns the directory catalogs are loaded from.
+fn locales_dir() -> PathBuf {
+    let configured = LOCALES_DIR
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    configured.unwrap_or_else(|| {
+        env::current_dir()
+            .map(|dir| dir.join("locales"))
+            .unwrap_or_else(|_| PathBuf::from("locales"))
+    })
+}
+
+/// Records a modification of the translation store.
+fn bump_generation() {
+    let _ = GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Records a replacement of the whole translation store, dropping the
+/// shared translators validated against the previous one.
+fn store_replaced() {
+    translator::clear_cache();
+    bump_generation();
+}
+
+/// Returns the translation store, loading it on first use.
+fn store() -> &'static RwLock<TranslationMap> {
+    TRANSLATIONS.get_or_init(|| RwLock::new(load_all_translations()))
+}
+
+/// Acquires shared access to the translation store.
+fn read_store() -> RwLockReadGuard<'static, TranslationMap> {
+    store().read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Acquires exclusive access to the translation store.
+fn write_store() -> RwLockWriteGuard<'static, TranslationMap> {
+    store().write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the catalog loaded for a language.
+///
+/// In strict mode languages outside `supported_languages` are rejected
+/// even when loaded.
+fn catalog<'a>(
+    store: &'a TranslationMap,
+    lang: &str,
+) -> Result<&'a Catalog, I18nError> {
+    if strict_rejects(lang) {
+        return Err(I18nError::UnsupportedLanguage(lang.to_string()));
+    }
+    store
+        .get(lang.to_lowercase().as_str())
+        .ok_or_else(|| I18nError::UnsupportedLanguage(lang.to_string()))
+}
+
+/// Returns the translations loaded for a language.
+fn language<'a>(
+    store: &'a TranslationMap,
+    lang: &str,
+) -> Result<&'a HashMap<String, String>, I18nError> {
+    catalog(store, lang).map(|catalog| &catalog.messages)
+}
+
+/// A function selecting the plural category for a count.
+type PluralRule = Arc<dyn Fn(u64) -> PluralCategory + Send + Sync>;
+
+/// Plural rules registered with `set_plural_rule`, keyed by language code.
+static PLURAL_RULES: Lazy<RwLock<HashMap<String, PluralRule>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// The CLDR plural categories a count can fall into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    /// The "zero" category (e.g., Arabic 0).
+    Zero,
+    /// The "one" category (e.g., English 1, French 0 and 1).
+    One,
+    /// The "two" category (e.g., Arabic 2).
+    Two,
+    /// The "few" category (e.g., Russian 2–4).
+    Few,
+    /// The "many" category (e.g., Russian 5–20).
+    Many,
+    /// The general category used when no other applies.
+    Other,
+}
+
+/// The opening of an ICU `select` block, such as `{gender, select,`.
+static SELECT_BLOCK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\s*\w+\s*,\s*select\s*,")
+        .expect("Failed to compile select regex")
+});
+
+/// The opening of an ICU `plural` block, such as `{count, plural,`.
+static PLURAL_BLOCK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\s*\w+\s*,\s*plural\s*,")
+        .expect("Failed to compile plural regex")
+});
+
+/// The store consulted by `translate_remote`, defaulting to the local catalog.
+static ASYNC_STORE: Lazy<RwLock<Arc<dyn AsyncTranslationStore>>> =
+    Lazy::new(|| RwLock::new(Arc::new(LocalStore)));
+
+/// A trait for asynchronous translation backends, such as a remote
+/// translation service or a CDN-hosted catalog.
+#[async_trait]
+pub trait AsyncTranslationStore: Send + Sync {
+    /// Looks up the translation of a key in the given language.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+    /// * `key` - A string slice that holds the key to be translated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(String))` - The translation if the store has one.
+    /// * `Ok(None)` - If the store has no translation for the key.
+    /// * `Err(I18nError)` - An error if the language is unsupported or the backend fails.
+    async fn get(
+        &self,
+        lang: &str,
+        key: &str,
+    ) -> Result<Option<String>, I18nError>;
+}
+
+/// An `AsyncTranslationStore` backed by the locally loaded `.po` catalog.
+///
+/// This is the default store used by `translate_remote`; its lookups
+/// complete immediately.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStore;
+
+#[async_trait]
+impl AsyncTranslationStore for LocalStore {
+    async fn get(
+        &self,
+        lang: &str,
+        key: &str,
+    ) -> Result<Option<String>, I18nError> {
+        let store = read_store();
+        let translations = language(&store, lang)?;
+        Ok(lookup(translations, key).cloned())
+    }
+}
+
+/// Replaces the store consulted by `translate_remote`.
+///
+/// # Arguments
+///
+/// * `store` - The asynchronous translation backend to use from now on.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{set_async_store, LocalStore};
+///
+/// set_async_store(Box::new(LocalStore));
+/// ```
+pub fn set_async_store(store: Box<dyn AsyncTranslationStore>) {
+    let mut current =
+        ASYNC_STORE.write().unwrap_or_else(PoisonError::into_inner);
+    *current = Arc::from(store);
+}
+
+/// Translates a key using the configured asynchronous store.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated string if found.
+/// * `Err(I18nError)` - An error if the translation fails or the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_remote;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     assert_eq!(translate_remote("fr", "Hello").await.unwrap(), "Bonjour");
+/// }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the store fails, or
+/// `I18nError::TranslationFailed` if it has no translation for the key.
+pub async fn translate_remote(
+    lang: &str,
+    key: &str,
+) -> Result<String, I18nError> {
+    if strict_rejects(lang) {
+        return Err(I18nError::UnsupportedLanguage(lang.to_string()));
+    }
+    let store = Arc::clone(
+        &ASYNC_STORE.read().unwrap_or_else(PoisonError::into_inner),
+    );
+    store.get(lang, key).await?.ok_or_else(|| {
+        I18nError::TranslationFailed(format!("{}:{}", lang, key))
+    })
+}
+
+/// Overrides the plural rule for a language.
+///
+/// The registered rule takes precedence over the built-in rule for that
+/// language, which is useful for dialects or constructed locales that the
+/// built-in rules get wrong or do not cover.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `rule` - A function mapping a count to its plural category.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{plural_category, set_plural_rule, PluralCategory};
+///
+/// set_plural_rule("x-simple", |_| PluralCategory::Other);
+/// assert_eq!(plural_category("x-simple", 1), PluralCategory::Other);
+/// ```
+pub fn set_plural_rule(
+    lang: &str,
+    rule: impl Fn(u64) -> PluralCategory + Send + Sync + 'static,
+) {
+    let mut rules =
+        PLURAL_RULES.write().unwrap_or_else(PoisonError::into_inner);
+    let _ = rules.insert(lang.to_lowercase(), Arc::new(rule));
+}
+
+/// Returns the plural category of a count in a language.
+///
+/// A rule registered with `set_plural_rule` is used if present. Otherwise
+/// the built-in rules apply: French, Portuguese and Hindi use "one" for 0
+/// and 1; Russian and Ukrainian distinguish "one", "few" and "many"; Arabic
+/// uses all six categories; Chinese, Japanese and Korean always use
+/// "other"; every other language, including English and German, uses "one"
+/// for exactly 1.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `n` - The count to categorise.
+///
+/// # Returns
+///
+/// * `PluralCategory` - The plural category of `n`.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{plural_category, PluralCategory};
+///
+/// assert_eq!(plural_category("en", 0), PluralCategory::Other);
+/// assert_eq!(plural_category("fr", 0), PluralCategory::One);
+/// assert_eq!(plural_category("ru", 3), PluralCategory::Few);
+/// ```
+pub fn plural_category(lang: &str, n: u64) -> PluralCategory {
+    let lang = lang.to_lowercase();
+    let custom = PLURAL_RULES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&lang)
+        .cloned();
+
+    match custom {
+        Some(rule) => rule(n),
+        None => builtin_plural_category(&lang, n),
+    }
+}
+
+/// Translates a key into the plural form matching `count`.
+///
+/// The forms come from gettext `msgid_plural` entries, whose `msgstr[n]`
+/// forms are ordered as in the language's `Plural-Forms` header: English,
+/// German and most languages use `n != 1` (singular, plural), French uses
+/// `n > 1`, Russian and Ukrainian have one, few and many forms, Arabic six,
+/// and Chinese, Japanese and Korean a single form. The form is chosen with
+/// `plural_category`, so rules registered with `set_plural_rule` apply.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the singular `msgid` of the entry.
+/// * `count` - The count selecting the plural form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The plural form for `count`.
+/// * `Err(I18nError)` - An error if the key has no plural forms or the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{register_embedded, translate_plural};
+///
+/// register_embedded(&[(
+///     "fr",
+///     "msgid \"One file\"\nmsgid_plural \"Files\"\nmsgstr[0] \"Un fichier\"\nmsgstr[1] \"Des fichiers\"\n",
+/// )]);
+/// assert_eq!(translate_plural("fr", "One file", 0).unwrap(), "Un fichier");
+/// assert_eq!(translate_plural("fr", "One file", 2).unwrap(), "Des fichiers");
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnsupportedLanguage` if the
+/// language is not loaded, or `I18nError::TranslationFailed` if the key
+/// has no plural forms.
+pub fn translate_plural(
+    lang: &str,
+    key: &str,
+    count: u64,
+) -> Result<String, I18nError> {
+    let store = read_store();
+    let forms = lookup(&catalog(&store, lang)?.plurals, key)
+        .ok_or_else(|| {
+            I18nError::TranslationFailed(format!("{}:{}", lang, key))
+        })?;
+
+    let index = plural_form_index(lang, count).min(forms.len() - 1);
+    Ok(forms[index].to_string())
+}
+
+/// Returns the index of the gettext plural form used for `n` in a
+/// language.
+fn plural_form_index(lang: &str, n: u64) -> usize {
+    use PluralCategory::*;
+
+    let order: &[PluralCategory] = match lang.to_lowercase().as_str() {
+        "ar" => &[Zero, One, Two, Few, Many, Other],
+        "ru" | "uk" => &[One, Few, Many],
+        "ja" | "ko" | "zh" => &[Other],
+        _ => &[One, Other],
+    };
+    let category = plural_category(lang, n);
+    order
+        .iter()
+        .position(|c| *c == category)
+        .unwrap_or(order.len() - 1)
+}
+
+/// Applies the built-in plural rule for a lowercase language code.
+fn builtin_plural_category(lang: &str, n: u64) -> PluralCategory {
+    let (mod10, mod100) = (n % 10, n % 100);
+    match lang {
+        "fr" | "pt" | "hi" if n <= 1 => PluralCategory::One,
+        "fr" | "pt" | "hi" => PluralCategory::Other,
+        "ja" | "ko" | "zh" => PluralCategory::Other,
+        "ru" | "uk" => {
+            if mod10 == 1 && mod100 != 11 {
+                PluralCategory::One
+            } else if (2..=4).contains(&mod10)
+                && !(12..=14).contains(&mod100)
+            {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "ar" => match n {
+            0 => PluralCategory::Zero,
+            1 => PluralCategory::One,
+            2 => PluralCategory::Two,
+            _ if (3..=10).contains(&mod100) => PluralCategory::Few,
+            _ if (11..=99).contains(&mod100) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        _ if n == 1 => PluralCategory::One,
+        _ => PluralCategory::Other,
+    }
+}
+
+/// Verifies that a translation catalog is valid UTF-8.
+///
+/// The whole file must decode as UTF-8 and, if the PO header declares a
+/// `charset`, it must be UTF-8 too. This catches catalogs saved in another
+/// encoding (such as Latin-1), which would otherwise load as garbled text.
+///
+/// # Arguments
+///
+/// * `path` - The path of the catalog file to check.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the file is valid UTF-8.
+/// * `Err(I18nError)` - An error describing the first encoding problem found.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::check_encoding;
+///
+/// assert!(check_encoding("locales/fr.po").is_ok());
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnexpectedError` if the file
+/// cannot be read, contains an invalid UTF-8 sequence (reporting its byte
+/// offset), or declares a charset other than UTF-8.
+pub fn check_encoding(path: impl AsRef<Path>) -> Result<(), I18nError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|e| {
+        I18nError::UnexpectedError(format!(
+            "Failed to read {:?}: {}",
+            path, e
+        ))
+    })?;
+
+    let content = std::str::from_utf8(&bytes).map_err(|e| {
+        I18nError::UnexpectedError(format!(
+            "Invalid UTF-8 in {:?} at byte offset {}",
+            path,
+            e.valid_up_to()
+        ))
+    })?;
+
+    match declared_charset(content) {
+        Some(charset)
+            if !charset.eq_ignore_ascii_case("utf-8")
+                && !charset.eq_ignore_ascii_case("utf8") =>
+        {
+            Err(I18nError::UnexpectedError(format!(
+                "{:?} declares unsupported charset {}",
+                path, charset
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Extracts the charset declared in a PO header's `Content-Type`, if any.
+///
+/// Only the header entry (the leading `msgid ""`) is searched, so a
+/// `charset=` inside a translated message is not mistaken for it.
+fn declared_charset(content: &str) -> Option<&str> {
+    let mut lines = content.lines().skip_while(|line| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with('#')
+    });
+    if lines.next()?.trim() != "msgid \"\"" {
+        return None;
+    }
+    lines
+        .take_while(|line| {
+            let line = line.trim_start();
+            !line.is_empty()
+                && !line.starts_with("msgid")
+                && !line.starts_with("msgctxt")
+        })
+        .find_map(|line| {
+            let start = line.find("charset=")? + "charset=".len();
+            line[start..]
+                .split(|c: char| {
+                    c.is_whitespace() || c == '\\' || c == '"'
+                })
+                .next()
+                .filter(|charset| !charset.is_empty())
+        })
+}
+
+/// Loads every catalog in `dir`.
+///
+/// Two layouts are supported and may be mixed: flat files named after
+/// their language (`locales/fr.po`), and one subdirectory per locale whose
+/// `.po` files are merged (`locales/fr-CA/messages.po`). Files that
+/// cannot be loaded are skipped and their errors pushed onto `errors`.
+fn load_translations_from_dir(
+    dir: &Path,
+    errors: &mut Vec<I18nError>,
+) -> TranslationMap {
     let mut all_translations = TranslationMap::new();
 
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if let Some(extension) = path.extension() {
-                if extension == "po" {
-                    if let Some(lang_code) =
-                        path.file_stem().and_then(|s| s.to_str())
-                    {
-                        match load_translations(&path) {
-                            Ok(translations) => {
-                                let _ = all_translations.insert(lang_code.to_lowercase(), translations);
-                            }
-                            Err(e) => eprintln!("Error loading translations for {:?}: {}", path, e),
-                        }
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if let Some(lang_code) =
+                    path.file_name().and_then(|s| s.to_str())
+                {
+                    let translations = load_locale_dir(&path, errors);
+                    if !translations.is_empty() {
+                        all_translations
+                            .entry(lang_code.to_lowercase())
+                            .or_default()
+                            .extend(translations);
+                    }
+                }
+            } else if is_translation_file(&path) {
+                if let Some(lang_code) =
+                    path.file_stem().and_then(|s| s.to_str())
+                {
+                    match parse_translation_file(&path) {
+                        Ok(translations) => {
+                            all_translations
+                                .entry(lang_code.to_lowercase())
+                                .or_default()
+                                .extend(translations);
+                        }
+                        Err(e) => errors.push(load_error(&path, e)),
+                    }
+                }
+            }
+        }
+    }
+
+    all_translations
+}
+
+/// Loads and merges every translation file in a locale subdirectory, in
+/// file name order so later files override earlier ones deterministically.
+fn load_locale_dir(dir: &Path, errors: &mut Vec<I18nError>) -> Catalog {
+    let mut paths: Vec<_> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && is_translation_file(path))
+            .collect(),
+        Err(_) => return Catalog::default(),
+    };
+    paths.sort();
+
+    let mut translations = Catalog::default();
+    for path in paths {
+        match parse_translation_file(&path) {
+            Ok(loaded) => translations.extend(loaded),
+            Err(e) => errors.push(load_error(&path, e)),
+        }
+    }
+    translations
+}
+
+/// Logs and wraps the error raised while loading a catalog file.
+fn load_error(path: &Path, e: std::io::Error) -> I18nError {
+    warn!("Skipping catalog {}: {}", path.display(), e);
+    I18nError::load_failed_with_source(path.display().to_string(), e)
+}
+
+/// The extensions of the translation file formats that can be loaded.
+#[cfg(feature = "yaml")]
+const TRANSLATION_EXTENSIONS: &[&str] = &["po", "json", "yaml", "yml"];
+/// The extensions of the translation file formats that can be loaded.
+#[cfg(not(feature = "yaml"))]
+const TRANSLATION_EXTENSIONS: &[&str] = &["po", "json"];
+
+/// Returns `true` if the path has the extension of a loadable format.
+fn is_translation_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| TRANSLATION_EXTENSIONS.contains(&ext))
+}
+
+/// Parses a translation file, choosing the format from its extension:
+/// gettext `.po`, or a flat key-to-translation object in `.json`, or in
+/// `.yaml` or `.yml` with the `yaml` feature.
+fn parse_translation_file(
+    path: &Path,
+) -> Result<Catalog, std::io::Error> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    let invalid = |e: &dyn fmt::Display| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            e.to_string(),
+        )
+    };
+
+    let messages = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("po") => return parse_po(reader),
+        Some("json") => {
+            serde_json::from_reader(reader).map_err(|e| invalid(&e))?
+        }
+        #[cfg(feature = "yaml")]
+        Some("yaml") | Some("yml") => {
+            serde_yaml::from_reader(reader).map_err(|e| invalid(&e))?
+        }
+        _ => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Unsupported translation file: {:?}", path),
+            ))
+        }
+    };
+    Ok(Catalog {
+        messages,
+        plurals: HashMap::new(),
+    })
+}
+
+/// Parses `msgid`/`msgstr` pairs from PO source.
+fn parse_po(reader: impl BufRead) -> Result<Catalog, std::io::Error> {
+    let mut translations = Catalog::default();
+    let mut current_msgid = String::new();
+    let mut plural_forms: Vec<String> = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.starts_with("msgid ") {
+            insert_plural_forms(
+                &mut translations,
+                &current_msgid,
+                &mut plural_forms,
+            );
+            current_msgid = parse_po_string(line, "msgid ");
+        } else if line.starts_with("msgstr[") {
+            if let Some(close) = line.find(']') {
+                plural_forms.push(parse_po_string(
+                    line[close + 1..].trim(),
+                    "",
+                ));
+            }
+        } else if line.starts_with("msgstr ") {
+            let msgstr = parse_po_string(line, "msgstr ");
+            if !current_msgid.is_empty() && !msgstr.is_empty() {
+                let _ = translations
+                    .messages
+                    .insert(current_msgid.clone(), msgstr);
+            }
+            current_msgid.clear();
+        }
+    }
+    insert_plural_forms(
+        &mut translations,
+        &current_msgid,
+        &mut plural_forms,
+    );
+
+    Ok(translations)
+}
+
+/// Stores the collected `msgstr[n]` forms of a plural entry.
+///
+/// The first form is also stored as the translation of the `msgid` so
+/// plain lookups find the singular.
+fn insert_plural_forms(
+    translations: &mut Catalog,
+    msgid: &str,
+    forms: &mut Vec<String>,
+) {
+    if !msgid.is_empty() && !forms.is_empty() && !forms[0].is_empty() {
+        let _ = translations
+            .messages
+            .insert(msgid.to_string(), forms[0].clone());
+        let _ = translations
+            .plurals
+            .insert(msgid.to_string(), mem::take(forms));
+    }
+    forms.clear();
+}
+
+/// Loads the catalogs in the locales directory, returning them with the
+/// errors of the catalogs that failed to load.
+///
+/// # Errors
+///
+/// Returns `I18nError::LoadFailed` if the locales directory does not
+/// exist.
+fn read_locales_dir(
+) -> Result<(TranslationMap, Vec<I18nError>), I18nError> {
+    let locales_dir = locales_dir();
+    if !locales_dir.is_dir() {
+        return Err(I18nError::load_failed(format!(
+            "locales directory {} not found",
+            locales_dir.display()
+        )));
+    }
+
+    debug!("Loading translations from {}", locales_dir.display());
+    let mut errors = Vec::new();
+    let translations =
+        load_translations_from_dir(&locales_dir, &mut errors);
+    Ok((translations, errors))
+}
+
+/// Loads the catalogs in the locales directory, skipping those that fail
+/// and starting empty if the directory does not exist.
+fn load_all_translations() -> TranslationMap {
+    let (translations, errors) =
+        read_locales_dir().unwrap_or_else(|error| {
+            debug!("{}, starting empty", error);
+            (TranslationMap::new(), Vec::new())
+        });
+    *LOAD_ERRORS.write().unwrap_or_else(PoisonError::into_inner) =
+        errors;
+    translations
+}
+
+fn parse_po_string(line: &str, prefix: &str) -> String {
+    line.trim_start_matches(prefix)
+        .trim_matches('"')
+        .replace("\\\"", "\"")
+}
+
+/// Translates a given key into the specified language.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated string if found.
+/// * `Err(I18nError)` - An error if the translation fails or the language is unsupported.
+pub fn translate(lang: &str, key: &str) -> Result<String, I18nError> {
+    let store = read_store();
+    let translations = language(&store, lang)?;
+
+    lookup(translations, key).cloned().ok_or_else(|| {
+        I18nError::TranslationFailed(format!("{}:{}", lang, key))
+    })
+}
+
+/// Passes the translation of a key to a closure without cloning it.
+///
+/// Keys are matched like `translate`: exactly first, then ignoring case.
+/// The borrow cannot outlive the call, so the translation store is only
+/// read-locked while `f` runs. `f` must not modify the store (with
+/// `update_key`, `init` and the like), as that would deadlock.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+/// * `f` - The closure receiving the borrowed translation.
+///
+/// # Returns
+///
+/// * `Option<R>` - The closure's result, or `None` if the language or key is missing.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_ref;
+///
+/// assert_eq!(translate_ref("fr", "Hello", str::len), Some(7));
+/// assert_eq!(translate_ref("fr", "Hello", |s| s.starts_with("Bon")), Some(true));
+/// assert!(translate_ref("fr", "Missing", str::len).is_none());
+/// ```
+pub fn translate_ref<R>(
+    lang: &str,
+    key: &str,
+    f: impl FnOnce(&str) -> R,
+) -> Option<R> {
+    let store = read_store();
+    language(&store, lang)
+        .ok()
+        .and_then(|translations| lookup(translations, key))
+        .map(|translation| f(translation))
+}
+
+/// Translates a given key, matching its case exactly.
+///
+/// Unlike `translate`, there is no case-insensitive fallback, so keys that
+/// differ only in case (such as `OK` and `Ok`) never match each other.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated string if found.
+/// * `Err(I18nError)` - An error if the translation fails or the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{translate, translate_exact};
+///
+/// assert_eq!(translate("fr", "hello").unwrap(), "Bonjour");
+/// assert!(translate_exact("fr", "hello").is_err());
+/// assert_eq!(translate_exact("fr", "Hello").unwrap(), "Bonjour");
+/// ```
+///
+/// # Errors
+///
+/// This function returns the same errors as `translate`.
+pub fn translate_exact(
+    lang: &str,
+    key: &str,
+) -> Result<String, I18nError> {
+    let store = read_store();
+    let translations = language(&store, lang)?;
+
+    translations.get(key).cloned().ok_or_else(|| {
+        I18nError::TranslationFailed(format!("{}:{}", lang, key))
+    })
+}
+
+/// Translates a given key, tolerating small typos in it.
+///
+/// Keys are matched like `translate` first. Failing that, the key closest
+/// to `key` by Levenshtein distance, ignoring case, is used if it is at
+/// most `max_distance` edits away; ties go to the smallest key. This scans
+/// every key of the language, so it is only available with the `fuzzy`
+/// feature.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+/// * `max_distance` - The largest number of single-character edits allowed.
+///
+/// # Returns
+///
+/// * `Ok((String, String))` - The matched key and its translation.
+/// * `Err(I18nError)` - An error if no key is close enough or the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_fuzzy;
+///
+/// let (key, translation) = translate_fuzzy("fr", "Helllo ", 2).unwrap();
+/// assert_eq!(key, "Hello");
+/// assert_eq!(translation, "Bonjour");
+/// assert!(translate_fuzzy("fr", "Helllo ", 1).is_err());
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnsupportedLanguage` if no
+/// translations are loaded for `lang`, and `I18nError::TranslationFailed`
+/// if no key is within `max_distance` edits.
+#[cfg(feature = "fuzzy")]
+pub fn translate_fuzzy(
+    lang: &str,
+    key: &str,
+    max_distance: usize,
+) -> Result<(String, String), I18nError> {
+    let store = read_store();
+    let translations = language(&store, lang)?;
+    if let Some((stored, value)) = lookup_entry(translations, key) {
+        return Ok((stored.clone(), value.clone()));
+    }
+
+    let wanted: Vec<char> = key.to_lowercase().chars().collect();
+    translations
+        .iter()
+        .filter_map(|(stored, value)| {
+            let candidate: Vec<char> =
+                stored.to_lowercase().chars().collect();
+            let distance = levenshtein(&wanted, &candidate);
+            if distance <= max_distance {
+                Some((distance, stored, value))
+            } else {
+                None
+            }
+        })
+        .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)))
+        .map(|(_, stored, value)| (stored.clone(), value.clone()))
+        .ok_or_else(|| {
+            I18nError::TranslationFailed(format!("{}:{}", lang, key))
+        })
+}
+
+/// Returns the number of single-character insertions, deletions and
+/// substitutions needed to turn `a` into `b`.
+#[cfg(feature = "fuzzy")]
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Translates a given key, carrying the key's capitalization over to the
+/// translation when it only matches ignoring case.
+///
+/// An exact match is returned untouched. Otherwise, an all-caps key yields
+/// an all-caps translation, and the case of the key's first letter is
+/// applied to the translation's first letter.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated string if found.
+/// * `Err(I18nError)` - An error if the translation fails or the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_preserve_case;
+///
+/// assert_eq!(translate_preserve_case("fr", "Hello").unwrap(), "Bonjour");
+/// assert_eq!(translate_preserve_case("fr", "hello").unwrap(), "bonjour");
+/// assert_eq!(translate_preserve_case("fr", "HELLO").unwrap(), "BONJOUR");
+/// ```
+///
+/// # Errors
+///
+/// This function returns the same errors as `translate`.
+pub fn translate_preserve_case(
+    lang: &str,
+    key: &str,
+) -> Result<String, I18nError> {
+    let store = read_store();
+    let translations = language(&store, lang)?;
+
+    match lookup_entry(translations, key) {
+        Some((stored, value)) if stored == key => Ok(value.clone()),
+        Some((_, value)) => Ok(apply_case(key, value)),
+        None => Err(I18nError::TranslationFailed(format!(
+            "{}:{}",
+            lang, key
+        ))),
+    }
+}
+
+/// Applies the capitalization pattern of `pattern` to `text`.
+fn apply_case(pattern: &str, text: &str) -> String {
+    let has_upper = pattern.chars().any(char::is_uppercase);
+    if has_upper && !pattern.chars().any(char::is_lowercase) {
+        return text.to_uppercase();
+    }
+
+    let leading = pattern.chars().find(|c| c.is_alphabetic());
+    let first = text.char_indices().find(|(_, c)| c.is_alphabetic());
+    match (leading, first) {
+        (Some(leading), Some((i, c))) => {
+            let rest = &text[i + c.len_utf8()..];
+            let c: String = if leading.is_uppercase() {
+                c.to_uppercase().collect()
+            } else if leading.is_lowercase() {
+                c.to_lowercase().collect()
+            } else {
+                c.to_string()
+            };
+            format!("{}{}{}", &text[..i], c, rest)
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Translates a key whose translation is an ICU-style `select` message.
+///
+/// A translation such as `{gender, select, male{He} female{She}
+/// other{They}} replied` has its select block replaced by the branch named
+/// `selector`, or by the `other` branch when no branch matches. Text
+/// around the block is kept, and a translation without a select block is
+/// returned unchanged.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+/// * `selector` - The branch to select (e.g., "female").
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translation with the selected branch.
+/// * `Err(I18nError)` - An error if the translation fails or has no matching branch.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{translate_select, update_key};
+///
+/// update_key("en", "replied", "{gender, select, male{He} female{She} other{They}} replied");
+/// assert_eq!(translate_select("en", "replied", "female").unwrap(), "She replied");
+/// assert_eq!(translate_select("en", "replied", "unknown").unwrap(), "They replied");
+/// ```
+///
+/// # Errors
+///
+/// This function returns the same errors as `translate`, and
+/// `I18nError::TranslationFailed` if the select block is malformed or has
+/// neither a matching nor an `other` branch.
+pub fn translate_select(
+    lang: &str,
+    key: &str,
+    selector: &str,
+) -> Result<String, I18nError> {
+    let message = translate(lang, key)?;
+    apply_select(&message, selector).ok_or_else(|| {
+        I18nError::TranslationFailed(format!(
+            "{}:{} has no branch for '{}'",
+            lang, key, selector
+        ))
+    })
+}
+
+/// Translates a given key and fills in its `{placeholder}` tokens.
+///
+/// Each `{name}` in the translation is replaced with the value of `name`
+/// in `args`; whitespace inside the braces is ignored. Placeholders
+/// without a value are left intact, and `{{` and `}}` produce a literal
+/// `{` and `}`.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+/// * `args` - The placeholder values, keyed by placeholder name.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated string with placeholders replaced.
+/// * `Err(I18nError)` - An error if the translation fails or the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{translate_with_args, update_key};
+/// use std::collections::HashMap;
+///
+/// update_key("en", "welcome", "Welcome, {name}! {{literal}} {unknown}");
+/// let mut args = HashMap::new();
+/// args.insert("name", "Ada".to_string());
+/// assert_eq!(
+///     translate_with_args("en", "welcome", &args).unwrap(),
+///     "Welcome, Ada! {literal} {unknown}"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// This function returns the same errors as `translate`.
+pub fn translate_with_args(
+    lang: &str,
+    key: &str,
+    args: &HashMap<&str, String>,
+) -> Result<String, I18nError> {
+    let message = translate(lang, key)?;
+    let args: Vec<(&str, &str)> = args
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    Ok(interpolate(&message, &args))
+}
+
+/// Replaces the first ICU `select` block of a message with the branch for
+/// `selector`, falling back to `other`.
+///
+/// Returns the message unchanged if it has no select block, and `None` if
+/// the block is malformed or no branch applies.
+fn apply_select(message: &str, selector: &str) -> Option<String> {
+    replace_block(message, &SELECT_BLOCK, |branches| {
+        find_branch(branches, selector)
+            .or_else(|| find_branch(branches, "other"))
+            .map(str::to_string)
+    })
+}
+
+/// Replaces the first ICU `plural` block of a message with the branch for
+/// `count`, substituting `#` with the formatted count.
+///
+/// An exact `=N` branch wins over the branch named after the plural
+/// category of `count` in `lang`, which wins over `other`. Returns the
+/// message unchanged if it has no plural block, and `None` if the block is
+/// malformed or no branch applies.
+pub(crate) fn apply_plural(
+    lang: &str,
+    message: &str,
+    count: u64,
+    formatted: &str,
+) -> Option<String> {
+    let category = match plural_category(lang, count) {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    };
+    let exact = format!("={}", count);
+    replace_block(message, &PLURAL_BLOCK, |branches| {
+        find_branch(branches, &exact)
+            .or_else(|| find_branch(branches, category))
+            .or_else(|| find_branch(branches, "other"))
+            .map(|branch| branch.replace('#', formatted))
+    })
+}
+
+/// Returns the text of the branch with the given name.
+fn find_branch<'a>(
+    branches: &[(&str, &'a str)],
+    name: &str,
+) -> Option<&'a str> {
+    branches
+        .iter()
+        .find(|(branch, _)| *branch == name)
+        .map(|(_, text)| *text)
+}
+
+/// Replaces the first ICU block opened by `block` with the text `choose`
+/// picks from its `(name, text)` branches.
+///
+/// Returns the message unchanged if it has no such block, and `None` if
+/// the block is malformed or `choose` returns `None`.
+fn replace_block(
+    message: &str,
+    block: &Regex,
+    choose: impl Fn(&[(&str, &str)]) -> Option<String>,
+) -> Option<String> {
+    let opening = match block.find(message) {
+        Some(opening) => opening,
+        None => return Some(message.to_string()),
+    };
+
+    let mut branches: Vec<(&str, &str)> = Vec::new();
+    let mut rest = &message[opening.end()..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix('}') {
+            let end = message.len() - after.len();
+            return Some(format!(
+                "{}{}{}",
+                &message[..opening.start()],
+                choose(&branches)?,
+                &message[end..]
+            ));
+        }
+
+        let open = rest.find('{')?;
+        let name = rest[..open].trim();
+        let mut depth = 0;
+        let mut close = None;
+        for (i, c) in rest[open..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = Some(open + i);
+                        break;
                     }
                 }
+                _ => {}
             }
         }
+        let close = close?;
+        if name.is_empty() {
+            return None;
+        }
+        branches.push((name, &rest[open + 1..close]));
+        rest = &rest[close + 1..];
     }
-
-    all_translations
 }
 
-fn load_translations(
-    file_path: &Path,
-) -> Result<HashMap<String, String>, std::io::Error> {
-    let file = fs::File::open(file_path)?;
-    let reader = BufReader::new(file);
-    let mut translations = HashMap::new();
-    let mut current_msgid = String::new();
-
-    for line in reader.lines() {
-        let line = line?;
-        let line = line.trim();
-
-        if line.starts_with("msgid ") {
-            current_msgid = parse_po_string(line, "msgid ");
-        } else if line.starts_with("msgstr ") {
-            let msgstr = parse_po_string(line, "msgstr ");
-            if !current_msgid.is_empty() && !msgstr.is_empty() {
-                let _ =
-                    translations.insert(current_msgid.clone(), msgstr);
+/// Replaces `{name}` placeholders with the matching argument values.
+///
+/// Placeholders without a matching argument are left intact, and `{{`
+/// and `}}` produce a literal `{` and `}`.
+pub(crate) fn interpolate(
+    message: &str,
+    args: &[(&str, &str)],
+) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(open) = rest.find(|c| c == '{' || c == '}') {
+        result.push_str(&rest[..open]);
+        let brace = &rest[open..open + 1];
+        let after = &rest[open + 1..];
+        if let Some(escaped) = after.strip_prefix(brace) {
+            result.push_str(brace);
+            rest = escaped;
+            continue;
+        }
+        if brace == "}" {
+            result.push('}');
+            rest = after;
+            continue;
+        }
+        let value = after.find('}').and_then(|close| {
+            let name = after[..close].trim();
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (*value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                result.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after;
             }
-            current_msgid.clear();
         }
     }
+    result.push_str(rest);
+    result
+}
 
-    Ok(translations)
+/// Formats an integer with the digit grouping separator of a language,
+/// such as `1,234` in English, `1.234` in German or `1 234` in French.
+pub(crate) fn format_integer(lang: &str, n: u64) -> String {
+    let separator = match lang.to_lowercase().as_str() {
+        "fr" => "\u{202F}",
+        "ru" | "uk" | "pl" | "cs" | "sv" | "fi" | "nb" => "\u{A0}",
+        "de" | "es" | "it" | "pt" | "nl" | "tr" | "id" | "da" => ".",
+        _ => ",",
+    };
+    let digits = n.to_string();
+    let mut result = String::with_capacity(digits.len() * 2);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            result.push_str(separator);
+        }
+        result.push(digit);
+    }
+    result
 }
 
-fn load_all_translations() -> TranslationMap {
-    let locales_dir = env::current_dir().unwrap().join("locales");
+/// Translates a given key and adds bidirectional control marks when needed.
+///
+/// Translations into right-to-left languages (such as Arabic and Hebrew) are
+/// wrapped in a Unicode right-to-left embedding (`U+202B` … `U+202C`) so they
+/// render correctly when embedded in left-to-right text that is not
+/// reordered by a bidi-aware renderer. Other translations are returned
+/// unchanged.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "ar", "fr").
+/// * `key` - A string slice that holds the key to be translated.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The translated string, wrapped for right-to-left languages.
+/// * `Err(I18nError)` - An error if the translation fails or the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_bidi;
+///
+/// // Left-to-right translations are not wrapped.
+/// assert_eq!(translate_bidi("fr", "Hello").unwrap(), "Bonjour");
+/// ```
+///
+/// # Errors
+///
+/// This function returns the same errors as `translate`.
+pub fn translate_bidi(
+    lang: &str,
+    key: &str,
+) -> Result<String, I18nError> {
+    translate(lang, key).map(|value| apply_bidi(lang, value))
+}
+
+/// Returns `true` if `lang`, or its primary subtag, is written
+/// right-to-left.
+pub(crate) fn is_rtl(lang: &str) -> bool {
+    let lang = lang.to_lowercase();
+    let primary = lang.split(|c| c == '-' || c == '_').next();
+    primary.map_or(false, |primary| RTL_LANGUAGES.contains(&primary))
+}
+
+/// Wraps text in a right-to-left embedding if `lang` is right-to-left.
+fn apply_bidi(lang: &str, text: String) -> String {
+    if is_rtl(lang) {
+        format!("{}{}{}", RLE, text, PDF)
+    } else {
+        text
+    }
+}
+
+/// Translates a key using a chain of languages, reporting which one matched.
+///
+/// Languages are tried in order and the first one with a translation for
+/// the key supplies the result. Knowing the source language lets callers set
+/// the correct `lang` attribute on the displayed text.
+///
+/// # Arguments
+///
+/// * `langs` - The language codes to try, in order of preference.
+/// * `key` - A string slice that holds the key to be translated.
+///
+/// # Returns
+///
+/// * `Ok((String, String))` - The translation and the language code that supplied it.
+/// * `Err(I18nError)` - An error if no language in the chain can translate the key.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_with_source;
+///
+/// let (value, source) = translate_with_source(&["xx", "de"], "Hello").unwrap();
+/// assert_eq!(value, "Hallo");
+/// assert_eq!(source, "de");
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnsupportedLanguage` if none of
+/// the languages are loaded, or `I18nError::TranslationFailed` if none of
+/// the loaded languages has a translation for the key.
+pub fn translate_with_source(
+    langs: &[&str],
+    key: &str,
+) -> Result<(String, String), I18nError> {
+    let store = read_store();
+    let mut any_supported = false;
 
-    if locales_dir.exists() && locales_dir.is_dir() {
-        println!("Locales directory found. Contents:");
-        if let Ok(entries) = fs::read_dir(&locales_dir) {
-            for entry in entries.flatten() {
-                println!("  {:?}", entry.path());
+    for lang in langs {
+        let lang = lang.to_lowercase();
+        if let Ok(translations) = language(&store, &lang) {
+            any_supported = true;
+            if let Some(value) = lookup(translations, key) {
+                return Ok((value.clone(), lang));
             }
         }
-        load_translations_from_dir(&locales_dir)
+    }
+
+    if any_supported || langs.is_empty() {
+        Err(I18nError::TranslationFailed(format!(
+            "{}:{}",
+            langs.join(","),
+            key
+        )))
     } else {
-        TranslationMap::new()
+        Err(I18nError::UnsupportedLanguage(langs.join(",")))
     }
 }
 
-fn parse_po_string(line: &str, prefix: &str) -> String {
-    line.trim_start_matches(prefix)
-        .trim_matches('"')
-        .replace("\\\"", "\"")
+/// Translates a key into several languages in one call.
+///
+/// The store is locked once for the whole call. Each language gets its own
+/// result, so an unsupported code or a missing translation does not
+/// affect the others.
+///
+/// # Arguments
+///
+/// * `langs` - The language codes to translate into (e.g., `["en", "fr", "de"]`).
+/// * `key` - A string slice that holds the key to be translated.
+///
+/// # Returns
+///
+/// * `HashMap<String, Result<String, I18nError>>` - Each requested language code mapped to its translation or error.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::error::I18nError;
+/// use langweave::translations::translate_many;
+///
+/// let previews = translate_many(&["fr", "de", "xx"], "Hello");
+/// assert_eq!(previews["fr"], Ok("Bonjour".to_string()));
+/// assert_eq!(previews["de"], Ok("Hallo".to_string()));
+/// assert!(matches!(previews["xx"], Err(I18nError::UnsupportedLanguage(_))));
+/// ```
+pub fn translate_many(
+    langs: &[&str],
+    key: &str,
+) -> HashMap<String, Result<String, I18nError>> {
+    let store = read_store();
+    langs
+        .iter()
+        .map(|lang| {
+            let result =
+                language(&store, lang).and_then(|translations| {
+                    lookup(translations, key).cloned().ok_or_else(
+                        || {
+                            I18nError::TranslationFailed(format!(
+                                "{}:{}",
+                                lang, key
+                            ))
+                        },
+                    )
+                });
+            (lang.to_string(), result)
+        })
+        .collect()
 }
 
-/// Translates a given key into the specified language.
+/// Translates every key into every requested language in one call.
+///
+/// The result is keyed first by language code and then by message key.
+/// Keys without a translation, and every key of an unsupported language,
+/// fall back to the key itself, or are left out in strict mode.
+///
+/// # Arguments
+///
+/// * `langs` - The language codes to translate into (e.g., `["fr", "de"]`).
+/// * `keys` - The message keys to translate.
+///
+/// # Returns
+///
+/// * `HashMap<String, HashMap<String, String>>` - The full language-by-key grid.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_matrix;
+///
+/// let matrix = translate_matrix(&["fr", "de"], &["Hello", "Missing"]);
+/// assert_eq!(matrix["fr"]["Hello"], "Bonjour");
+/// assert_eq!(matrix["de"]["Hello"], "Hallo");
+/// assert_eq!(matrix["de"]["Missing"], "Missing");
+/// ```
+pub fn translate_matrix(
+    langs: &[&str],
+    keys: &[&str],
+) -> HashMap<String, HashMap<String, String>> {
+    let store = read_store();
+    langs
+        .iter()
+        .map(|lang| {
+            let translations = language(&store, lang).ok();
+            let row = keys
+                .iter()
+                .filter_map(|key| {
+                    let value = match translations
+                        .and_then(|t| lookup(t, key))
+                    {
+                        Some(value) => value.clone(),
+                        None if is_strict() => return None,
+                        None => key.to_string(),
+                    };
+                    Some((key.to_string(), value))
+                })
+                .collect();
+            (lang.to_string(), row)
+        })
+        .collect()
+}
+
+/// Exports the translations of a chosen set of keys.
+///
+/// Only the requested keys are included, which keeps bundles shipped to a
+/// single screen small. Keys without a translation, or every key if the
+/// language is not loaded, map to themselves, or are left out in strict
+/// mode.
 ///
 /// # Arguments
 ///
 /// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
-/// * `key` - A string slice that holds the key to be translated.
+/// * `keys` - The keys to export.
 ///
 /// # Returns
 ///
-/// * `Ok(String)` - The translated string if found.
-/// * `Err(I18nError)` - An error if the translation fails or the language is unsupported.
-pub fn translate(lang: &str, key: &str) -> Result<String, I18nError> {
-    let translations =
-        TRANSLATIONS.get(lang.to_lowercase().as_str()).ok_or_else(
-            || I18nError::UnsupportedLanguage(lang.to_string()),
-        )?;
+/// * `HashMap<String, String>` - Each requested key mapped to its translation, or to itself.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::export_subset;
+///
+/// let bundle = export_subset("fr", &["Hello", "Goodbye", "Checkout"]);
+/// assert_eq!(bundle.len(), 3);
+/// assert_eq!(bundle["Hello"], "Bonjour");
+/// assert_eq!(bundle["Checkout"], "Checkout");
+/// ```
+pub fn export_subset(
+    lang: &str,
+    keys: &[&str],
+) -> HashMap<String, String> {
+    let store = read_store();
+    let translations = language(&store, lang).ok();
+    keys.iter()
+        .filter_map(|key| {
+            let value = match translations
+                .and_then(|translations| lookup(translations, key))
+            {
+                Some(value) => value.as_str(),
+                None if is_strict() => return None,
+                None => key,
+            };
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Reports, for every loaded language, whether a key is translated.
+///
+/// In strict mode languages outside `supported_languages` are left out.
+///
+/// # Arguments
+///
+/// * `key` - A string slice that holds the key to look up.
+///
+/// # Returns
+///
+/// * `HashMap<String, bool>` - Each loaded language code mapped to whether it has the key.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::key_availability;
+///
+/// let availability = key_availability("Hello");
+/// assert_eq!(availability.get("fr"), Some(&true));
+/// assert!(!key_availability("Unshipped")["de"]);
+/// ```
+pub fn key_availability(key: &str) -> HashMap<String, bool> {
+    read_store()
+        .iter()
+        .filter(|(lang, _)| !strict_rejects(lang))
+        .map(|(lang, catalog)| {
+            (lang.clone(), lookup(&catalog.messages, key).is_some())
+        })
+        .collect()
+}
+
+/// Reports the keys each loaded language is missing.
+///
+/// The union of the keys of every loaded language is computed once, and
+/// each language is mapped to the keys of that union it does not define.
+/// A catalog in sync with the others maps to an empty list. In strict
+/// mode languages outside `supported_languages` are left out, and their
+/// keys do not count towards the union.
+///
+/// # Returns
+///
+/// * `HashMap<String, Vec<String>>` - Each loaded language code mapped to its missing keys, sorted.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::{coverage_report, update_key};
+///
+/// update_key("en", "beta_banner", "Try the beta");
+/// let report = coverage_report();
+/// assert!(report["fr"].contains(&"beta_banner".to_string()));
+/// assert!(!report["en"].contains(&"beta_banner".to_string()));
+/// ```
+pub fn coverage_report() -> HashMap<String, Vec<String>> {
+    let store = read_store();
+    let catalogs: Vec<(&String, &Catalog)> = store
+        .iter()
+        .filter(|(lang, _)| !strict_rejects(lang))
+        .collect();
+    let all_keys: BTreeSet<&String> = catalogs
+        .iter()
+        .flat_map(|(_, catalog)| catalog.messages.keys())
+        .collect();
+
+    catalogs
+        .into_iter()
+        .map(|(lang, catalog)| {
+            let missing = all_keys
+                .iter()
+                .filter(|key| {
+                    !catalog.messages.contains_key(key.as_str())
+                })
+                .map(|key| (*key).clone())
+                .collect();
+            (lang.clone(), missing)
+        })
+        .collect()
+}
+
+/// Returns every translation whose key matches a glob pattern.
+///
+/// In the pattern, `*` matches any run of characters and `?` matches a
+/// single character; everything else matches literally.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "en", "fr").
+/// * `pattern` - A glob pattern over message keys (e.g., `checkout.*`).
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, String>)` - The matching keys with their translations.
+/// * `Err(I18nError)` - An error if the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::translate_matching;
+///
+/// let logs = translate_matching("fr", "lib_*_log_msg").unwrap();
+/// assert!(logs.contains_key("lib_banner_log_msg"));
+/// assert!(!logs.contains_key("Hello"));
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnsupportedLanguage` if no
+/// translations are loaded for `lang`.
+pub fn translate_matching(
+    lang: &str,
+    pattern: &str,
+) -> Result<HashMap<String, String>, I18nError> {
+    let store = read_store();
+    let translations = language(&store, lang)?;
+    let matcher = glob_to_regex(pattern)?;
+
+    Ok(translations
+        .iter()
+        .filter(|(key, _)| matcher.is_match(key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect())
+}
+
+/// Returns every key loaded for a language.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code (e.g., "fr").
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - The keys, sorted.
+/// * `Err(I18nError)` - An error if the language is unsupported.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::available_keys;
+///
+/// let keys = available_keys("fr").unwrap();
+/// assert!(keys.contains(&"Hello".to_string()));
+/// assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+/// assert!(available_keys("xx").is_err());
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnsupportedLanguage` if no
+/// translations are loaded for `lang`.
+pub fn available_keys(lang: &str) -> Result<Vec<String>, I18nError> {
+    let store = read_store();
+    let mut keys: Vec<String> =
+        language(&store, lang)?.keys().cloned().collect();
+    keys.sort();
+    Ok(keys)
+}
+
+/// Returns the smallest key whose translation in `lang` is `translated`.
+///
+/// # Errors
+///
+/// Returns `I18nError::UnsupportedLanguage` if no translations are loaded
+/// for `lang`, and `I18nError::TranslationFailed` if no key maps to
+/// `translated`.
+pub(crate) fn key_for(
+    lang: &str,
+    translated: &str,
+) -> Result<String, I18nError> {
+    let store = read_store();
+    language(&store, lang)?
+        .iter()
+        .filter(|(_, value)| *value == translated)
+        .map(|(key, _)| key)
+        .min()
+        .cloned()
+        .ok_or_else(|| {
+            I18nError::TranslationFailed(translated.to_string())
+        })
+}
+
+/// Compiles a glob pattern into an anchored regular expression.
+fn glob_to_regex(pattern: &str) -> Result<Regex, I18nError> {
+    let mut source = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => source.push_str(".*"),
+            '?' => source.push('.'),
+            _ => source.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    source.push('$');
+
+    Regex::new(&source).map_err(|e| {
+        I18nError::UnexpectedError(format!(
+            "Invalid key pattern '{}': {}",
+            pattern, e
+        ))
+    })
+}
+
+/// Detects the language of every value in a language's catalog.
+///
+/// This is a sanity check for translator submissions: an entry whose value
+/// is detected as a different language (typically the source language) was
+/// most likely left untranslated.
+///
+/// # Arguments
+///
+/// * `lang` - A string slice that holds the language code of the catalog to audit.
+///
+/// # Returns
+///
+/// * `Vec<(String, Option<String>)>` - Each key, sorted, paired with the detected
+///   language of its value, or `None` if it could not be detected. Unsupported
+///   languages yield an empty vector.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::audit_languages;
+///
+/// let audit = audit_languages("fr");
+/// let lang_of = |key: &str| {
+///     audit.iter().find(|(k, _)| k == key).and_then(|(_, l)| l.clone())
+/// };
+/// assert_eq!(lang_of("Hello"), Some("fr".to_string()));
+/// ```
+pub fn audit_languages(lang: &str) -> Vec<(String, Option<String>)> {
+    let store = read_store();
+    let translations = match language(&store, lang) {
+        Ok(translations) => translations,
+        Err(_) => return Vec::new(),
+    };
+
+    let detector = LanguageDetector::new();
+    let mut audit: Vec<(String, Option<String>)> = translations
+        .iter()
+        .map(|(key, value)| (key.clone(), detector.detect(value).ok()))
+        .collect();
+    audit.sort();
+    audit
+}
+
+/// Returns the typical length ratio of text translated between languages.
+///
+/// The ratio comes from a built-in table of empirical averages for UI
+/// text (German runs about 35% longer than English, Chinese about half as
+/// long). It is a fallback for `measured_expansion` when catalogs are too
+/// sparse to measure.
+///
+/// # Arguments
+///
+/// * `from` - A string slice that holds the source language code (e.g., "en").
+/// * `to` - A string slice that holds the target language code (e.g., "de").
+///
+/// # Returns
+///
+/// * `Option<f64>` - The expected target length divided by the source length, or `None` if either language is not in the table.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::expansion_ratio;
+///
+/// assert_eq!(expansion_ratio("en", "de"), Some(1.35));
+/// assert_eq!(expansion_ratio("xx", "de"), None);
+/// ```
+pub fn expansion_ratio(from: &str, to: &str) -> Option<f64> {
+    let factor = |lang: &str| {
+        let lang = lang.to_lowercase();
+        EXPANSION_FACTORS
+            .iter()
+            .find(|(l, _)| *l == lang)
+            .map(|(_, factor)| *factor)
+    };
+    Some(factor(to)? / factor(from)?)
+}
+
+/// Measures the length ratio of text translated between two languages
+/// from the loaded catalogs.
+///
+/// Only keys translated in both languages are compared, and the ratio is
+/// their total length in characters in `to` divided by their total length
+/// in `from`.
+///
+/// # Arguments
+///
+/// * `from` - A string slice that holds the source language code (e.g., "en").
+/// * `to` - A string slice that holds the target language code (e.g., "de").
+///
+/// # Returns
+///
+/// * `Option<f64>` - The measured ratio, or `None` if either language is not loaded or they share no keys.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::translations::measured_expansion;
+///
+/// let ratio = measured_expansion("en", "de").unwrap();
+/// assert!(ratio > 1.0);
+/// ```
+pub fn measured_expansion(from: &str, to: &str) -> Option<f64> {
+    let store = read_store();
+    let source = language(&store, from).ok()?;
+    let target = language(&store, to).ok()?;
+
+    let (mut source_len, mut target_len) = (0, 0);
+    for (key, value) in source {
+        if let Some(translation) = target.get(key) {
+            source_len += value.chars().count();
+            target_len += translation.chars().count();
+        }
+    }
+
+    if source_len == 0 {
+        None
+    } else {
+        Some(target_len as f64 / source_len as f64)
+    }
+}
+
+/// Returns the translated text of every loaded language, joined into one
+/// sample per language and sorted by language code.
+pub(crate) fn corpora() -> Vec<(String, String)> {
+    let store = read_store();
+    let mut corpora: Vec<(String, String)> = store
+        .iter()
+        .map(|(lang, catalog)| {
+            let text = catalog
+                .messages
+                .values()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join("\n");
+            (lang.clone(), text)
+        })
+        .collect();
+    corpora.sort();
+    corpora
+}
 
-    // Try exact match first
-    if let Some(translation) = translations.get(key) {
-        return Ok(translation.clone());
-    }
+/// Looks up a key in a single language's translations, trying an exact
+/// match first and then a case-insensitive one.
+fn lookup<'a, V>(
+    translations: &'a HashMap<String, V>,
+    key: &str,
+) -> Option<&'a V> {
+    lookup_entry(translations, key).map(|(_, v)| v)
+}
 
-    // If not found, try case-insensitive match
-    for (k, v) in translations {
-        if k.to_lowercase() == key.to_lowercase() {
-            return Ok(v.clone());
-        }
+/// Finds the entry for a key, trying an exact match first and then a
+/// case-insensitive one.
+fn lookup_entry<'a, V>(
+    translations: &'a HashMap<String, V>,
+    key: &str,
+) -> Option<(&'a String, &'a V)> {
+    if let Some(entry) = translations.get_key_value(key) {
+        return Some(entry);
     }
 
-    Err(I18nError::TranslationFailed(format!("{}:{}", lang, key)))
+    let key = key.to_lowercase();
+    translations.iter().find(|(k, _)| k.to_lowercase() == key)
 }
 
 #[cfg(test)]
@@ -126,18 +2398,20 @@ mod tests {
     #[test]
     fn print_locales_contents() {
         let locales_dir = env::current_dir().unwrap().join("locales");
-        println!("Locales directory: {:?}", locales_dir);
-        if locales_dir.exists() && locales_dir.is_dir() {
-            println!("Contents:");
-            if let Ok(entries) = fs::read_dir(&locales_dir) {
-                for entry in entries.flatten() {
-                    println!("  {:?}", entry.path());
-                }
-            }
-        } else {
-            println!(
-                "Locales directory not found or is not a directory."
-            );
+        assert!(
+            locales_dir.is_dir(),
+            "Locales directory not found or is not a directory: {:?}",
+            locales_dir
+        );
+        let entries: Vec<PathBuf> = fs::read_dir(&locales_dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        assert!(entries.iter().any(|path| is_translation_file(path)));
+        for lang in &["en", "fr", "de"] {
+            assert!(entries
+                .contains(&locales_dir.join(format!("{}.po", lang))));
         }
     }
 
@@ -163,7 +2437,12 @@ fn test_po_files_exist() {
 
     #[test]
     fn print_loaded_translations() {
-        println!("Loaded translations: {:#?}", *TRANSLATIONS);
+        let store = read_store();
+        for lang in &["en", "fr", "de"] {
+            let catalog = &store[*lang];
+            assert!(!catalog.messages.is_empty(), "{} is empty", lang);
+            assert!(catalog.messages.contains_key("Hello"));
+        }
     }
 
     #[test]
@@ -233,9 +2512,754 @@ fn test_unsupported_language() {
         ));
     }
 
+    #[test]
+    fn test_builtin_plural_rules() {
+        use PluralCategory::*;
+        let cases = [
+            ("en", 1, One),
+            ("en", 0, Other),
+            ("de", 2, Other),
+            ("fr", 0, One),
+            ("fr", 1, One),
+            ("fr", 2, Other),
+            ("ja", 1, Other),
+            ("ru", 1, One),
+            ("ru", 11, Many),
+            ("ru", 22, Few),
+            ("ru", 25, Many),
+            ("ar", 0, Zero),
+            ("ar", 2, Two),
+            ("ar", 105, Few),
+            ("ar", 111, Many),
+            ("ar", 100, Other),
+            ("xx", 1, One),
+            ("xx", 7, Other),
+        ];
+        for (lang, n, expected) in cases {
+            assert_eq!(
+                plural_category(lang, n),
+                expected,
+                "{} {}",
+                lang,
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_plural_rule() {
+        assert_eq!(
+            plural_category("x-plural", 2),
+            PluralCategory::Other
+        );
+        set_plural_rule("X-Plural", |n| {
+            if n == 2 {
+                PluralCategory::Two
+            } else {
+                PluralCategory::Other
+            }
+        });
+        assert_eq!(plural_category("x-plural", 2), PluralCategory::Two);
+        assert_eq!(
+            plural_category("x-plural", 1),
+            PluralCategory::Other
+        );
+    }
+
+    #[test]
+    fn test_register_embedded() {
+        register_embedded(&[
+            ("EN", "msgid \"embedded_key\"\nmsgstr \"Embedded\"\n"),
+            ("x-test", "msgid \"embedded_key\"\nmsgstr \"Test\"\n"),
+        ]);
+        assert_eq!(
+            translate("en", "embedded_key").unwrap(),
+            "Embedded"
+        );
+        assert_eq!(
+            translate("x-test", "embedded_key").unwrap(),
+            "Test"
+        );
+        // Existing entries are preserved when merging.
+        assert_eq!(translate("en", "Hello").unwrap(), "Hello");
+    }
+
+    #[test]
+    fn test_incremental_updates() {
+        let before = generation();
+        update_key("X-Live", "greeting", "Hi");
+        assert_eq!(translate("x-live", "greeting").unwrap(), "Hi");
+        assert!(generation() > before);
+
+        update_key("x-live", "greeting", "Hey");
+        assert_eq!(translate("x-live", "greeting").unwrap(), "Hey");
+
+        assert!(remove_key("x-live", "greeting"));
+        assert!(matches!(
+            translate("x-live", "greeting"),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(!remove_key("x-live", "greeting"));
+        assert!(!remove_key("x-missing", "greeting"));
+    }
+
+    #[test]
+    fn test_load_locale_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("de.po"),
+            "msgid \"Hello\"\nmsgstr \"Hallo\"\n",
+        )
+        .unwrap();
+        let fr_ca = dir.path().join("fr-CA");
+        fs::create_dir(&fr_ca).unwrap();
+        fs::write(
+            fr_ca.join("messages.po"),
+            "msgid \"Hello\"\nmsgstr \"Allô\"\nmsgid \"Bye\"\nmsgstr \"Bye\"\n",
+        )
+        .unwrap();
+        fs::write(
+            fr_ca.join("shop.po"),
+            "msgid \"Bye\"\nmsgstr \"Salut\"\nmsgid \"Cart\"\nmsgstr \"Panier\"\n",
+        )
+        .unwrap();
+        fs::write(fr_ca.join("notes.txt"), "ignored").unwrap();
+        fs::create_dir(dir.path().join("empty")).unwrap();
+
+        let loaded =
+            load_translations_from_dir(dir.path(), &mut Vec::new());
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded["de"].messages["Hello"], "Hallo");
+        let fr_ca = &loaded["fr-ca"].messages;
+        assert_eq!(fr_ca.len(), 3);
+        assert_eq!(fr_ca["Hello"], "Allô");
+        assert_eq!(fr_ca["Bye"], "Salut");
+        assert_eq!(fr_ca["Cart"], "Panier");
+    }
+
+    #[test]
+    fn test_load_json() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("es.json"),
+            r#"{"Hello": "Hola", "Goodbye": "Adiós"}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("pt.json"), "[1, 2]").unwrap();
+
+        let mut errors = Vec::new();
+        let loaded =
+            load_translations_from_dir(dir.path(), &mut errors);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded["es"].messages["Goodbye"], "Adiós");
+
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("pt.json"));
+        let error = parse_translation_file(&dir.path().join("pt.json"))
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_load_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("it.yaml"),
+            "Hello: Ciao\n\"Good night\": Buona notte\n",
+        )
+        .unwrap();
+        let nl = dir.path().join("nl");
+        fs::create_dir(&nl).unwrap();
+        fs::write(nl.join("a.po"), "msgid \"Hello\"\nmsgstr \"Hoi\"\n")
+            .unwrap();
+        fs::write(nl.join("b.yml"), "Hello: Hallo\nBye: Doei\n")
+            .unwrap();
+
+        let mut errors = Vec::new();
+        let loaded =
+            load_translations_from_dir(dir.path(), &mut errors);
+        assert!(errors.is_empty());
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded["it"].messages["Good night"], "Buona notte");
+        assert_eq!(loaded["nl"].messages["Hello"], "Hallo");
+        assert_eq!(loaded["nl"].messages["Bye"], "Doei");
+    }
+
+    #[test]
+    fn test_check_encoding() {
+        let dir = tempfile::tempdir().unwrap();
+
+        for lang in &["en", "fr", "de"] {
+            assert!(
+                check_encoding(format!("locales/{}.po", lang)).is_ok()
+            );
+        }
+
+        let latin1 = dir.path().join("latin1.po");
+        fs::write(&latin1, b"msgid \"Caf\xe9\"\nmsgstr \"\"\n")
+            .unwrap();
+        match check_encoding(&latin1) {
+            Err(I18nError::UnexpectedError(msg)) => {
+                assert!(msg.contains("byte offset 10"), "{}", msg)
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        let declared = dir.path().join("declared.po");
+        fs::write(
+            &declared,
+            "msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=ISO-8859-1\\n\"\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            check_encoding(&declared),
+            Err(I18nError::UnexpectedError(msg)) if msg.contains("ISO-8859-1")
+        ));
+
+        // A charset mentioned in a message does not count as declared
+        let message = dir.path().join("message.po");
+        fs::write(
+            &message,
+            "# Catalog\nmsgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n\nmsgid \"hint\"\nmsgstr \"Use charset=ISO-8859-1\"\n",
+        )
+        .unwrap();
+        assert!(check_encoding(&message).is_ok());
+
+        let headerless = dir.path().join("headerless.po");
+        fs::write(
+            &headerless,
+            "msgid \"hint\"\nmsgstr \"charset=latin1\"\n",
+        )
+        .unwrap();
+        assert!(check_encoding(&headerless).is_ok());
+
+        assert!(check_encoding(dir.path().join("missing.po")).is_err());
+    }
+
+    #[test]
+    fn test_translate_bidi() {
+        assert_eq!(translate_bidi("de", "Hello").unwrap(), "Hallo");
+        assert!(matches!(
+            translate_bidi("ar", "Hello"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+        assert_eq!(
+            apply_bidi("AR", "مرحبا".to_string()),
+            "\u{202B}مرحبا\u{202C}"
+        );
+        assert_eq!(
+            apply_bidi("he", "שלום".to_string()).chars().count(),
+            6
+        );
+        assert_eq!(apply_bidi("en", "Hello".to_string()), "Hello");
+    }
+
+    #[test]
+    fn test_translate_with_source() {
+        assert_eq!(
+            translate_with_source(&["fr", "en"], "Hello").unwrap(),
+            ("Bonjour".to_string(), "fr".to_string())
+        );
+        assert_eq!(
+            translate_with_source(&["xx", "DE"], "Goodbye").unwrap(),
+            ("Auf Wiedersehen".to_string(), "de".to_string())
+        );
+        assert!(matches!(
+            translate_with_source(&["fr", "de"], "Missing"),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(matches!(
+            translate_with_source(&["xx", "yy"], "Hello"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_translate_many() {
+        let results = translate_many(&["EN", "fr", "xx"], "hello");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results["EN"], Ok("Hello".to_string()));
+        assert_eq!(results["fr"], Ok("Bonjour".to_string()));
+        assert_eq!(
+            results["xx"],
+            Err(I18nError::UnsupportedLanguage("xx".to_string()))
+        );
+        assert!(matches!(
+            translate_many(&["de"], "x-many-missing")["de"],
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(translate_many(&[], "Hello").is_empty());
+    }
+
+    #[test]
+    fn test_translate_matrix() {
+        let matrix = translate_matrix(
+            &["en", "fr", "xx"],
+            &["Hello", "Unknown"],
+        );
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix["en"]["Hello"], "Hello");
+        assert_eq!(matrix["fr"]["Hello"], "Bonjour");
+        assert_eq!(matrix["fr"]["Unknown"], "Unknown");
+        assert_eq!(matrix["xx"]["Hello"], "Hello");
+    }
+
+    #[test]
+    fn test_key_availability() {
+        update_key("x-rollout", "new_feature", "Ready");
+        let availability = key_availability("new_feature");
+        assert!(availability["x-rollout"]);
+        assert!(!availability["fr"]);
+        assert!(key_availability("hello").values().any(|&found| found));
+    }
+
+    #[cfg(feature = "fuzzy")]
+    #[test]
+    fn test_translate_fuzzy() {
+        update_key("en", "x-fuzzy-alpha", "Alpha");
+        update_key("en", "x-fuzzy-alpht", "Alpht");
+        assert_eq!(
+            translate_fuzzy("en", "X-FUZZY-ALPHA", 0).unwrap(),
+            ("x-fuzzy-alpha".to_string(), "Alpha".to_string())
+        );
+        // Both keys are one edit away; the smallest wins
+        assert_eq!(
+            translate_fuzzy("en", "x-fuzzy-alphx", 1).unwrap().0,
+            "x-fuzzy-alpha"
+        );
+        assert!(matches!(
+            translate_fuzzy("en", "x-fuzzy-zzzzz", 2),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(matches!(
+            translate_fuzzy("xx", "Hello", 3),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(levenshtein(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(levenshtein(&chars(""), &chars("abc")), 3);
+        assert_eq!(levenshtein(&chars("héllo"), &chars("hello")), 1);
+    }
+
+    #[test]
+    fn test_translate_preserve_case() {
+        update_key("en", "x-case Key", "iPhone case");
+        assert_eq!(
+            translate_preserve_case("en", "x-case Key").unwrap(),
+            "iPhone case"
+        );
+        assert_eq!(
+            translate_preserve_case("en", "X-CASE KEY").unwrap(),
+            "IPHONE CASE"
+        );
+        assert_eq!(
+            translate_preserve_case("en", "X-case key").unwrap(),
+            "IPhone case"
+        );
+        assert_eq!(
+            translate_preserve_case("de", "goodbye").unwrap(),
+            "auf Wiedersehen"
+        );
+        assert!(
+            translate_preserve_case("en", "x-case missing").is_err()
+        );
+        assert_eq!(apply_case("¡hola!", "¡Bien!"), "¡bien!");
+        assert_eq!(apply_case("42", "Done"), "Done");
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        update_key("en", "x-coverage", "Covered");
+        let report = coverage_report();
+        assert!(report["de"].contains(&"x-coverage".to_string()));
+        assert!(!report["en"].contains(&"x-coverage".to_string()));
+        assert!(report["de"].windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_translate_matching() {
+        let all = translate_matching("de", "*").unwrap();
+        assert_eq!(all.len(), read_store()["de"].messages.len());
+
+        let logs = translate_matching("de", "lib_*_log_msg").unwrap();
+        assert_eq!(logs.len(), 3);
+        assert!(logs["lib_server_log_msg"].contains("Server"));
+
+        let single = translate_matching("en", "Ye?").unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single["Yes"], "Yes");
+
+        assert!(translate_matching("fr", "Hello.*")
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            translate_matching("xx", "*"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_available_keys() {
+        update_key("en", "x-available", "Available");
+        register_embedded(&[(
+            "en",
+            "msgid \"x-available-file\"\nmsgid_plural \"Files\"\n\
+             msgstr[0] \"File\"\nmsgstr[1] \"Files\"\n",
+        )]);
+        let keys = available_keys("EN").unwrap();
+        assert!(keys.contains(&"x-available".to_string()));
+        assert!(keys.contains(&"x-available-file".to_string()));
+        assert_eq!(
+            keys.iter().filter(|key| key.contains('\0')).count(),
+            0
+        );
+        assert!(matches!(
+            available_keys("xx"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_audit_languages() {
+        let audit = audit_languages("de");
+        assert_eq!(audit.len(), read_store()["de"].messages.len());
+        assert!(audit.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert!(audit
+            .iter()
+            .any(|(k, l)| k == "Hello" && l.as_deref() == Some("de")));
+        assert!(audit_languages("xx").is_empty());
+    }
+
+    #[test]
+    fn test_expansion_ratio() {
+        assert_eq!(expansion_ratio("EN", "de"), Some(1.35));
+        assert_eq!(expansion_ratio("de", "de"), Some(1.0));
+        let ratio = expansion_ratio("en", "zh").unwrap();
+        assert!((ratio - 0.5).abs() < f64::EPSILON);
+        assert_eq!(expansion_ratio("en", "xx"), None);
+    }
+
+    #[test]
+    fn test_measured_expansion() {
+        let ratio = measured_expansion("en", "fr").unwrap();
+        assert!(ratio > 1.0 && ratio < 2.0, "{}", ratio);
+        assert_eq!(measured_expansion("en", "xx"), None);
+    }
+
+    struct UppercaseStore;
+
+    #[async_trait]
+    impl AsyncTranslationStore for UppercaseStore {
+        async fn get(
+            &self,
+            _lang: &str,
+            key: &str,
+        ) -> Result<Option<String>, I18nError> {
+            Ok(Some(key.to_uppercase()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_translate_remote() {
+        assert_eq!(
+            LocalStore.get("de", "Hello").await.unwrap(),
+            Some("Hallo".to_string())
+        );
+        assert_eq!(
+            LocalStore.get("de", "Missing").await.unwrap(),
+            None
+        );
+        assert!(matches!(
+            translate_remote("xx", "Hello").await,
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+        assert!(matches!(
+            translate_remote("fr", "Missing").await,
+            Err(I18nError::TranslationFailed(_))
+        ));
+
+        set_async_store(Box::new(UppercaseStore));
+        assert_eq!(
+            translate_remote("xx", "hello").await.unwrap(),
+            "HELLO"
+        );
+
+        set_async_store(Box::new(LocalStore));
+        assert_eq!(
+            translate_remote("fr", "Hello").await.unwrap(),
+            "Bonjour"
+        );
+    }
+
     #[test]
     fn test_case_sensitivity() {
         assert_eq!(translate("en", "hello").unwrap(), "Hello");
         assert_eq!(translate("fr", "GOODBYE").unwrap(), "Au revoir");
     }
+
+    #[test]
+    fn test_apply_select() {
+        let message =
+            "{gender, select, male{He} female{She} other{They}} left";
+        assert_eq!(apply_select(message, "male").unwrap(), "He left");
+        assert_eq!(
+            apply_select(message, "other").unwrap(),
+            "They left"
+        );
+        assert_eq!(
+            apply_select(message, "robot").unwrap(),
+            "They left"
+        );
+        assert_eq!(
+            apply_select(
+                "Hi {role,select,admin{{boss}} other{you}}!",
+                "admin"
+            )
+            .unwrap(),
+            "Hi {boss}!"
+        );
+        assert_eq!(
+            apply_select("Plain text", "male").unwrap(),
+            "Plain text"
+        );
+        assert_eq!(
+            apply_select("{g, select, male{He}}", "female"),
+            None
+        );
+        assert_eq!(apply_select("{g, select, male{He}", "male"), None);
+    }
+
+    #[test]
+    fn test_export_subset() {
+        let bundle =
+            export_subset("DE", &["Hello", "goodbye", "Missing"]);
+        assert_eq!(bundle.len(), 3);
+        assert_eq!(bundle["Hello"], "Hallo");
+        assert_eq!(bundle["goodbye"], "Auf Wiedersehen");
+        assert_eq!(bundle["Missing"], "Missing");
+
+        let bundle = export_subset("xx", &["Hello"]);
+        assert_eq!(bundle["Hello"], "Hello");
+        assert!(export_subset("fr", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let before = memory_usage();
+        assert!(before > 0);
+        update_key("en", "x-memory-usage", &"y".repeat(10_000));
+        assert!(memory_usage() >= before + 10_000);
+    }
+
+    #[test]
+    fn test_apply_plural() {
+        let message =
+            "{n, plural, =0{No files} one{# file} other{# files}}!";
+        assert_eq!(
+            apply_plural("en", message, 0, "0").unwrap(),
+            "No files!"
+        );
+        assert_eq!(
+            apply_plural("en", message, 1, "1").unwrap(),
+            "1 file!"
+        );
+        assert_eq!(
+            apply_plural("en", message, 1200, "1,200").unwrap(),
+            "1,200 files!"
+        );
+        assert_eq!(
+            apply_plural(
+                "ru",
+                "{n, plural, few{# x} other{y}}",
+                3,
+                "3"
+            )
+            .unwrap(),
+            "3 x"
+        );
+        assert_eq!(
+            apply_plural("en", "Plain", 2, "2").unwrap(),
+            "Plain"
+        );
+        assert!(
+            apply_plural("en", "{n, plural, one{a}}", 2, "2").is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_po_plurals() {
+        let source =
+            "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2;\"\n\n\
+                      msgid \"One apple\"\nmsgid_plural \"Apples\"\n\
+                      msgstr[0] \"Ein Apfel\"\nmsgstr[1] \"Äpfel\"\n\n\
+                      msgid \"Pear\"\nmsgstr \"Birne\"\n\n\
+                      msgid \"Plum\"\nmsgid_plural \"Plums\"\n\
+                      msgstr[0] \"Pflaume\"\nmsgstr[1] \"Pflaumen\"";
+        let catalog = parse_po(source.as_bytes()).unwrap();
+        assert_eq!(catalog.messages.len(), 3);
+        assert_eq!(catalog.messages["One apple"], "Ein Apfel");
+        assert_eq!(catalog.messages["Pear"], "Birne");
+        assert_eq!(catalog.plurals.len(), 2);
+        assert_eq!(
+            catalog.plurals["One apple"],
+            ["Ein Apfel", "Äpfel"]
+        );
+        assert_eq!(catalog.plurals["Plum"], ["Pflaume", "Pflaumen"]);
+    }
+
+    #[test]
+    fn test_translate_plural() {
+        register_embedded(&[(
+            "en",
+            "msgid \"x-plural-file\"\nmsgid_plural \"files\"\n\
+             msgstr[0] \"a file\"\nmsgstr[1] \"# files\"\n",
+        )]);
+        assert_eq!(translate("en", "x-plural-file").unwrap(), "a file");
+        register_embedded(&[(
+            "en",
+            "msgid \"Été-x-plural\"\nmsgid_plural \"Étés\"\n\
+             msgstr[0] \"One summer\"\nmsgstr[1] \"# summers\"\n",
+        )]);
+        assert_eq!(
+            translate("en", "ÉTÉ-X-PLURAL").unwrap(),
+            "One summer"
+        );
+        assert_eq!(
+            translate_plural("en", "été-x-plural", 3).unwrap(),
+            "# summers"
+        );
+        assert!(translate("en", "x-plural-file\0").is_err());
+        assert_eq!(
+            translate_plural("en", "x-plural-file", 1).unwrap(),
+            "a file"
+        );
+        assert_eq!(
+            translate_plural("en", "x-plural-file", 0).unwrap(),
+            "# files"
+        );
+
+        assert_eq!(plural_form_index("en", 1), 0);
+        assert_eq!(plural_form_index("de", 2), 1);
+        assert_eq!(plural_form_index("fr", 0), 0);
+        assert_eq!(plural_form_index("fr", 2), 1);
+        assert_eq!(plural_form_index("ru", 3), 1);
+        assert_eq!(plural_form_index("ru", 5), 2);
+        assert_eq!(plural_form_index("ja", 1), 0);
+        assert_eq!(plural_form_index("x-unknown", 7), 1);
+
+        assert!(matches!(
+            translate_plural("en", "Hello", 2),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(matches!(
+            translate_plural("xx", "Hello", 2),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_translate_with_args() {
+        update_key(
+            "en",
+            "x-args-greeting",
+            "Hi {name}, you owe {{ {amount}",
+        );
+        let mut args = HashMap::new();
+        let _ = args.insert("name", "Bo".to_string());
+        assert_eq!(
+            translate_with_args("en", "x-args-greeting", &args)
+                .unwrap(),
+            "Hi Bo, you owe { {amount}"
+        );
+        let _ = args.insert("amount", "5 €".to_string());
+        assert_eq!(
+            translate_with_args("en", "x-args-greeting", &args)
+                .unwrap(),
+            "Hi Bo, you owe { 5 €"
+        );
+        assert!(matches!(
+            translate_with_args("xx", "Hello", &args),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let args = [("name", "Ada"), ("count", "3")];
+        assert_eq!(
+            interpolate("Hi {name}, {count} new", &args),
+            "Hi Ada, 3 new"
+        );
+        assert_eq!(interpolate("{ name }!", &args), "Ada!");
+        assert_eq!(
+            interpolate("{missing} {name}", &args),
+            "{missing} Ada"
+        );
+        assert_eq!(interpolate("{{name}} {", &args), "{name} {");
+        assert_eq!(interpolate("a } b {name}}", &args), "a } b Ada}");
+    }
+
+    #[test]
+    fn test_format_integer() {
+        assert_eq!(format_integer("en", 0), "0");
+        assert_eq!(format_integer("en", 999), "999");
+        assert_eq!(format_integer("en", 1234567), "1,234,567");
+        assert_eq!(format_integer("de", 1000), "1.000");
+        assert_eq!(format_integer("FR", 12345), "12\u{202F}345");
+    }
+
+    #[test]
+    fn test_translate_select() {
+        update_key(
+            "x-select",
+            "invite",
+            "{gender, select, female{Elle} other{Il}} vous invite",
+        );
+        assert_eq!(
+            translate_select("x-select", "invite", "female").unwrap(),
+            "Elle vous invite"
+        );
+        assert_eq!(
+            translate_select("x-select", "invite", "male").unwrap(),
+            "Il vous invite"
+        );
+        assert!(matches!(
+            translate_select("x-select", "missing", "male"),
+            Err(I18nError::TranslationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_translate_ref() {
+        assert_eq!(
+            translate_ref("DE", "goodbye", str::to_uppercase).unwrap(),
+            "AUF WIEDERSEHEN"
+        );
+        assert!(translate_ref("de", "Missing", str::len).is_none());
+        assert!(translate_ref("xx", "Hello", str::len).is_none());
+
+        // The store is unlocked once the closure returns
+        let len = translate_ref("en", "Hello", str::len).unwrap();
+        update_key("en", "x-after-ref", "Updated");
+        assert_eq!(len, 5);
+        assert_eq!(translate("en", "x-after-ref").unwrap(), "Updated");
+    }
+
+    #[test]
+    fn test_translate_exact() {
+        update_key("x-case", "OK", "Okay");
+        update_key("x-case", "Ok", "Fine");
+        assert_eq!(translate_exact("x-case", "OK").unwrap(), "Okay");
+        assert_eq!(translate_exact("x-case", "Ok").unwrap(), "Fine");
+        assert!(matches!(
+            translate_exact("x-case", "ok"),
+            Err(I18nError::TranslationFailed(_))
+        ));
+        assert!(matches!(
+            translate_exact("xx", "OK"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
 }
@@ -3,13 +3,29 @@
 //! This module provides functionality to translate text into different languages.
 
 use crate::error::I18nError;
+use crate::sanitize_input;
 use crate::translations;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Shared translators created by `Translator::cached`, keyed by language
+/// code.
+static CACHE: Lazy<Mutex<HashMap<String, Arc<Translator>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 /// A struct responsible for translating text into different languages.
 #[derive(Debug, Clone)]
 pub struct Translator {
     lang: String,
+    fallback: Option<String>,
+    overrides: HashMap<String, String>,
+}
+
+/// Drops every translator shared by `Translator::cached`.
+pub(crate) fn clear_cache() {
+    CACHE.lock().unwrap_or_else(PoisonError::into_inner).clear();
 }
 
 impl Translator {
@@ -35,7 +51,11 @@ pub fn new(lang: &str) -> Result<Self, I18nError> {
         let lang = lang.to_lowercase();
         // Check if the language is supported by trying to translate a known key
         match translations::translate(&lang, "Hello") {
-            Ok(_) => Ok(Translator { lang }),
+            Ok(_) => Ok(Translator {
+                lang,
+                fallback: None,
+                overrides: HashMap::new(),
+            }),
             Err(I18nError::UnsupportedLanguage(_)) => {
                 Err(I18nError::UnsupportedLanguage(lang))
             }
@@ -43,11 +63,131 @@ pub fn new(lang: &str) -> Result<Self, I18nError> {
         }
     }
 
+    /// Returns a shared `Translator` for a specific language.
+    ///
+    /// The first call for a language creates the translator like `new`;
+    /// later calls return the same instance, so hot languages are not
+    /// validated again on every use. The shared instances are dropped
+    /// whenever the whole translation store is replaced or reloaded, so a
+    /// language that is no longer loaded is rejected again.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - A string slice that holds the language code (e.g., "en", "fr", "de")
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Arc<Translator>, I18nError>` - The shared translator instance or an error if the language is unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::translator::Translator;
+    /// use std::sync::Arc;
+    ///
+    /// let first = Translator::cached("fr").unwrap();
+    /// let second = Translator::cached("FR").unwrap();
+    /// assert!(Arc::ptr_eq(&first, &second));
+    /// assert_eq!(second.translate("Hello").unwrap(), "Bonjour");
+    /// ```
+    pub fn cached(lang: &str) -> Result<Arc<Self>, I18nError> {
+        let key = lang.to_lowercase();
+        if let Some(translator) = CACHE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+        {
+            return Ok(Arc::clone(translator));
+        }
+
+        // Validate outside the lock; a concurrent caller may win the race,
+        // in which case its instance is kept.
+        let translator = Arc::new(Translator::new(&key)?);
+        Ok(Arc::clone(
+            CACHE
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(key)
+                .or_insert(translator),
+        ))
+    }
+
+    /// Creates a `Translator` that falls back to another language.
+    ///
+    /// Keys missing from `lang` are looked up in `fallback` before an error
+    /// is returned. The fallback only applies to this instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - A string slice that holds the language code (e.g., "fr")
+    /// * `fallback` - A string slice that holds the fallback language code (e.g., "en")
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Translator, I18nError>` - The translator instance or an error if either language is unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::translations::update_key;
+    /// use langweave::translator::Translator;
+    ///
+    /// update_key("en", "checkout_title", "Checkout");
+    /// let translator = Translator::with_fallback("fr", "en").unwrap();
+    /// assert_eq!(translator.translate("Hello").unwrap(), "Bonjour");
+    /// assert_eq!(translator.translate("checkout_title").unwrap(), "Checkout");
+    /// ```
+    pub fn with_fallback(
+        lang: &str,
+        fallback: &str,
+    ) -> Result<Self, I18nError> {
+        let fallback = Translator::new(fallback)?.lang;
+        let mut translator = Translator::new(lang)?;
+        translator.fallback = Some(fallback);
+        Ok(translator)
+    }
+
+    /// Creates a `Translator` with instance-scoped term overrides.
+    ///
+    /// Lookups check `overrides` first and only then the shared catalog, so
+    /// domain- or customer-specific terms can be translated without
+    /// modifying the global store.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - A string slice that holds the language code (e.g., "fr")
+    /// * `overrides` - Translations keyed by source text, consulted before the catalog
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Translator, I18nError>` - The translator instance or an error if the language is unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::translator::Translator;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut overrides = HashMap::new();
+    /// overrides.insert("Hello".to_string(), "Salut".to_string());
+    /// let translator = Translator::with_overrides("fr", overrides).unwrap();
+    /// assert_eq!(translator.translate("Hello").unwrap(), "Salut");
+    /// assert_eq!(translator.translate("Goodbye").unwrap(), "Au revoir");
+    /// ```
+    pub fn with_overrides(
+        lang: &str,
+        overrides: HashMap<String, String>,
+    ) -> Result<Self, I18nError> {
+        let mut translator = Translator::new(lang)?;
+        translator.overrides = overrides;
+        Ok(translator)
+    }
+
     /// Translates the given text.
     ///
     /// # Arguments
     ///
-    /// * `text` - A string slice that holds the text to be translated
+    /// * `text` - The text to be translated, as a `&str`, `String` or `&String`
     ///
     /// # Returns
     ///
@@ -60,9 +200,124 @@ pub fn new(lang: &str) -> Result<Self, I18nError> {
     ///
     /// let translator = Translator::new("fr").unwrap();
     /// assert_eq!(translator.translate("Hello").unwrap(), "Bonjour");
+    ///
+    /// let key = String::from("Goodbye");
+    /// assert_eq!(translator.translate(&key).unwrap(), "Au revoir");
     /// ```
-    pub fn translate(&self, text: &str) -> Result<String, I18nError> {
-        translations::translate(&self.lang, text)
+    pub fn translate(
+        &self,
+        text: impl AsRef<str>,
+    ) -> Result<String, I18nError> {
+        let text = sanitize_input(text.as_ref());
+        if let Some(translation) = self.overrides.get(text.as_ref()) {
+            return Ok(translation.clone());
+        }
+        match (
+            translations::translate(&self.lang, &text),
+            &self.fallback,
+        ) {
+            (Err(I18nError::TranslationFailed(_)), Some(fallback)) => {
+                translations::translate(fallback, &text)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Translates several keys at once, returning the results in input
+    /// order.
+    ///
+    /// Each key is translated as by `translate`, so overrides and the
+    /// fallback language apply, and a missing key only fails its own entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to be translated, as `&str`, `String` or `&String` items
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Result<String, I18nError>>` - One result per key, in the order the keys were given
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::translator::Translator;
+    ///
+    /// let translator = Translator::new("fr").unwrap();
+    /// let results = translator.translate_all(["Hello", "Missing", "Goodbye"]);
+    /// assert_eq!(results[0].as_deref().unwrap(), "Bonjour");
+    /// assert!(results[1].is_err());
+    /// assert_eq!(results[2].as_deref().unwrap(), "Au revoir");
+    /// ```
+    pub fn translate_all(
+        &self,
+        keys: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Vec<Result<String, I18nError>> {
+        keys.into_iter().map(|key| self.translate(key)).collect()
+    }
+
+    /// Recovers the key that translates to the given text.
+    ///
+    /// Overrides are searched first, then the shared catalog. When several
+    /// keys translate to the same text, the first in sorted order is
+    /// returned so the result is deterministic.
+    ///
+    /// # Arguments
+    ///
+    /// * `translated` - A string slice that holds the translated text (e.g., "Bonjour")
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, I18nError>` - The matching key or an error if no key translates to `translated`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::translator::Translator;
+    ///
+    /// let translator = Translator::new("fr").unwrap();
+    /// assert_eq!(translator.reverse_translate("Bonjour").unwrap(), "Hello");
+    /// assert!(translator.reverse_translate("Hola").is_err());
+    /// ```
+    pub fn reverse_translate(
+        &self,
+        translated: &str,
+    ) -> Result<String, I18nError> {
+        let overridden = self
+            .overrides
+            .iter()
+            .filter(|(_, value)| *value == translated)
+            .map(|(key, _)| key)
+            .min();
+        match overridden {
+            Some(key) => Ok(key.clone()),
+            None => translations::key_for(&self.lang, translated),
+        }
+    }
+
+    /// Returns every key this translator can translate.
+    ///
+    /// This covers the catalog of the translator's language and its
+    /// overrides, but not the keys only available through the fallback.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The keys, sorted and without duplicates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use langweave::translator::Translator;
+    ///
+    /// let translator = Translator::new("de").unwrap();
+    /// assert!(translator.keys().contains(&"Goodbye".to_string()));
+    /// ```
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys = translations::available_keys(&self.lang)
+            .unwrap_or_default();
+        keys.extend(self.overrides.keys().cloned());
+        keys.sort();
+        keys.dedup();
+        keys
     }
 
     /// Returns the language code of this translator.
@@ -123,6 +378,153 @@ fn test_case_insensitive_language_code() {
         assert_eq!(translator.translate("Hello").unwrap(), "Bonjour");
     }
 
+    #[test]
+    fn test_with_overrides() {
+        let mut overrides = HashMap::new();
+        let _ = overrides
+            .insert("Goodbye".to_string(), "Tschüss".to_string());
+        let _ = overrides
+            .insert("ticket".to_string(), "Vorgang".to_string());
+        let translator =
+            Translator::with_overrides("de", overrides).unwrap();
+        assert_eq!(translator.translate("Goodbye").unwrap(), "Tschüss");
+        assert_eq!(translator.translate("ticket").unwrap(), "Vorgang");
+        assert_eq!(translator.translate("Hello").unwrap(), "Hallo");
+
+        let shared = Translator::new("de").unwrap();
+        assert_eq!(
+            shared.translate("Goodbye").unwrap(),
+            "Auf Wiedersehen"
+        );
+        assert!(
+            Translator::with_overrides("xx", HashMap::new()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_reverse_translate() {
+        translations::update_key("en", "x-reverse-b", "Twin");
+        translations::update_key("en", "x-reverse-a", "Twin");
+        let translator = Translator::new("en").unwrap();
+        assert_eq!(
+            translator.reverse_translate("Twin").unwrap(),
+            "x-reverse-a"
+        );
+        assert!(matches!(
+            translator.reverse_translate("x-reverse-none"),
+            Err(I18nError::TranslationFailed(_))
+        ));
+
+        let mut overrides = HashMap::new();
+        let _ =
+            overrides.insert("Goodbye".to_string(), "Ciao".to_string());
+        let translator =
+            Translator::with_overrides("fr", overrides).unwrap();
+        assert_eq!(
+            translator.reverse_translate("Ciao").unwrap(),
+            "Goodbye"
+        );
+        assert_eq!(
+            translator.reverse_translate("Bonjour").unwrap(),
+            "Hello"
+        );
+    }
+
+    #[test]
+    fn test_translate_all() {
+        let mut overrides = HashMap::new();
+        let _ =
+            overrides.insert("Hello".to_string(), "Salut".to_string());
+        let translator =
+            Translator::with_overrides("fr", overrides).unwrap();
+
+        let keys = vec!["Yes".to_string(), "Missing".to_string()];
+        let results = translator.translate_all(&keys);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), "Oui");
+        assert!(matches!(
+            &results[1],
+            Err(I18nError::TranslationFailed(_))
+        ));
+
+        let results = translator.translate_all(vec!["Hello", "No"]);
+        assert_eq!(
+            results.into_iter().collect::<Result<Vec<_>, _>>().unwrap(),
+            vec!["Salut".to_string(), "Non".to_string()]
+        );
+        assert!(translator
+            .translate_all(Vec::<&str>::new())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_keys() {
+        translations::update_key("en", "x-keys", "Listed");
+        let mut overrides = HashMap::new();
+        let _ =
+            overrides.insert("x-keys".to_string(), "Mine".to_string());
+        let _ = overrides
+            .insert("x-keys-override".to_string(), "Own".to_string());
+        let translator =
+            Translator::with_overrides("en", overrides).unwrap();
+        let keys = translator.keys();
+        assert_eq!(
+            keys.iter().filter(|key| key.starts_with("x-keys")).count(),
+            2
+        );
+        assert!(keys.contains(&"Hello".to_string()));
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_cached() {
+        let first = Translator::cached("De").unwrap();
+        let second = Translator::cached("de").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.lang(), "de");
+        assert!(matches!(
+            Translator::cached("xx"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| std::thread::spawn(|| Translator::cached("en")))
+            .collect();
+        let translators: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap().unwrap())
+            .collect();
+        assert!(translators
+            .windows(2)
+            .all(|pair| Arc::ptr_eq(&pair[0], &pair[1])));
+    }
+
+    #[test]
+    fn test_with_fallback() {
+        translations::update_key("en", "fallback_only", "English only");
+        let translator = Translator::with_fallback("FR", "en").unwrap();
+        assert_eq!(translator.lang(), "fr");
+        assert_eq!(translator.translate("Hello").unwrap(), "Bonjour");
+        assert_eq!(
+            translator.translate("fallback_only").unwrap(),
+            "English only"
+        );
+        assert!(matches!(
+            translator.translate("Missing everywhere"),
+            Err(I18nError::TranslationFailed(_))
+        ));
+
+        // Plain translators are unaffected.
+        assert!(Translator::new("fr")
+            .unwrap()
+            .translate("fallback_only")
+            .is_err());
+        assert!(matches!(
+            Translator::with_fallback("fr", "xx"),
+            Err(I18nError::UnsupportedLanguage(_))
+        ));
+    }
+
     #[test]
     fn test_display_implementation() {
         let translator = Translator::new("en").unwrap();
@@ -0,0 +1,483 @@
+// Copyright © 2024 LangWeave. All rights reserved.
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Transliteration Module
+//!
+//! This module romanizes text written in Cyrillic or in Japanese kana, for
+//! logging or storage that is restricted to Latin characters. It is only
+//! available with the `transliteration` feature.
+//!
+//! Cyrillic is romanized following common practice for Russian and
+//! Ukrainian, with per-language tables for the letters Belarusian,
+//! Bulgarian and Macedonian read differently, and Serbian is written in
+//! its own Latin alphabet. Kana is romanized following the Hepburn
+//! system. Characters outside
+//! the tables, such as Latin letters, digits, punctuation or kanji, are
+//! kept as they are.
+
+use crate::error::I18nError;
+use crate::script::Script;
+
+/// The languages written in Cyrillic that can be transliterated.
+const CYRILLIC_LANGUAGES: &[&str] =
+    &["be", "bg", "mk", "ru", "sr", "uk"];
+
+/// The romanization of each lowercase Cyrillic letter.
+const CYRILLIC: &[(char, &str)] = &[
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('ґ', "g"),
+    ('д', "d"),
+    ('е', "e"),
+    ('ё', "yo"),
+    ('є', "ye"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('і', "i"),
+    ('ї', "yi"),
+    ('й', "y"),
+    ('к', "k"),
+    ('л', "l"),
+    ('м', "m"),
+    ('н', "n"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('у', "u"),
+    ('ў', "w"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+];
+
+/// The Ukrainian romanization of letters read differently than in Russian.
+const UKRAINIAN: &[(char, &str)] =
+    &[('г', "h"), ('и', "y"), ('ї', "i")];
+
+/// The Belarusian romanization of letters read differently than in
+/// Russian.
+const BELARUSIAN: &[(char, &str)] = &[('г', "h")];
+
+/// The Bulgarian romanization of letters read differently than in
+/// Russian, following the Streamlined System.
+const BULGARIAN: &[(char, &str)] =
+    &[('х', "h"), ('щ', "sht"), ('ъ', "a"), ('ь', "y")];
+
+/// The Macedonian romanization of letters that differ from Russian,
+/// including the letters Russian does not use.
+const MACEDONIAN: &[(char, &str)] = &[
+    ('ѓ', "gj"),
+    ('ѕ', "dz"),
+    ('ј', "j"),
+    ('љ', "lj"),
+    ('њ', "nj"),
+    ('ќ', "kj"),
+    ('х', "h"),
+    ('ц', "c"),
+    ('џ', "dzh"),
+];
+
+/// The Serbian Latin alphabet letters of Cyrillic letters that differ
+/// from Russian, including the letters Russian does not use.
+const SERBIAN: &[(char, &str)] = &[
+    ('ђ', "đ"),
+    ('ж', "ž"),
+    ('ј', "j"),
+    ('љ', "lj"),
+    ('њ', "nj"),
+    ('ћ', "ć"),
+    ('х', "h"),
+    ('ц', "c"),
+    ('ч', "č"),
+    ('џ', "dž"),
+    ('ш', "š"),
+];
+
+/// The Hepburn romanization of each hiragana syllable. Katakana is
+/// mapped onto hiragana before lookup.
+const KANA: &[(char, &str)] = &[
+    ('ぁ', "a"),
+    ('あ', "a"),
+    ('ぃ', "i"),
+    ('い', "i"),
+    ('ぅ', "u"),
+    ('う', "u"),
+    ('ぇ', "e"),
+    ('え', "e"),
+    ('ぉ', "o"),
+    ('お', "o"),
+    ('か', "ka"),
+    ('が', "ga"),
+    ('き', "ki"),
+    ('ぎ', "gi"),
+    ('く', "ku"),
+    ('ぐ', "gu"),
+    ('け', "ke"),
+    ('げ', "ge"),
+    ('こ', "ko"),
+    ('ご', "go"),
+    ('さ', "sa"),
+    ('ざ', "za"),
+    ('し', "shi"),
+    ('じ', "ji"),
+    ('す', "su"),
+    ('ず', "zu"),
+    ('せ', "se"),
+    ('ぜ', "ze"),
+    ('そ', "so"),
+    ('ぞ', "zo"),
+    ('た', "ta"),
+    ('だ', "da"),
+    ('ち', "chi"),
+    ('ぢ', "ji"),
+    ('つ', "tsu"),
+    ('づ', "zu"),
+    ('て', "te"),
+    ('で', "de"),
+    ('と', "to"),
+    ('ど', "do"),
+    ('な', "na"),
+    ('に', "ni"),
+    ('ぬ', "nu"),
+    ('ね', "ne"),
+    ('の', "no"),
+    ('は', "ha"),
+    ('ば', "ba"),
+    ('ぱ', "pa"),
+    ('ひ', "hi"),
+    ('び', "bi"),
+    ('ぴ', "pi"),
+    ('ふ', "fu"),
+    ('ぶ', "bu"),
+    ('ぷ', "pu"),
+    ('へ', "he"),
+    ('べ', "be"),
+    ('ぺ', "pe"),
+    ('ほ', "ho"),
+    ('ぼ', "bo"),
+    ('ぽ', "po"),
+    ('ま', "ma"),
+    ('み', "mi"),
+    ('む', "mu"),
+    ('め', "me"),
+    ('も', "mo"),
+    ('ゃ', "ya"),
+    ('や', "ya"),
+    ('ゅ', "yu"),
+    ('ゆ', "yu"),
+    ('ょ', "yo"),
+    ('よ', "yo"),
+    ('ら', "ra"),
+    ('り', "ri"),
+    ('る', "ru"),
+    ('れ', "re"),
+    ('ろ', "ro"),
+    ('ゎ', "wa"),
+    ('わ', "wa"),
+    ('ゐ', "i"),
+    ('ゑ', "e"),
+    ('を', "o"),
+    ('ん', "n"),
+    ('ゔ', "vu"),
+];
+
+/// The small kana that combine with a preceding `i` syllable (`きゃ`).
+const SMALL_Y: &[char] = &['ゃ', 'ゅ', 'ょ'];
+
+/// The small `tsu` that doubles the following consonant (`きって`).
+const SOKUON: char = 'っ';
+
+/// The prolonged sound mark that lengthens the preceding vowel (`コーヒー`).
+const CHOONPU: char = 'ー';
+
+/// Romanizes text written in the script of the given language.
+///
+/// Cyrillic is supported for Belarusian, Bulgarian, Macedonian, Russian,
+/// Serbian and Ukrainian, and kana for Japanese. Text that is already
+/// written in Latin characters is returned unchanged whatever the
+/// language.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text to romanize.
+/// * `from_lang` - A string slice that holds the language code of the text (e.g., "ru", "ja").
+///
+/// # Returns
+///
+/// * `Ok(String)` - The romanized text.
+/// * `Err(I18nError)` - An error if the language's script has no transliteration table.
+///
+/// # Examples
+///
+/// ```
+/// use langweave::transliteration::transliterate;
+///
+/// assert_eq!(transliterate("Привет, мир", "ru").unwrap(), "Privet, mir");
+/// assert_eq!(transliterate("ありがとう", "ja").unwrap(), "arigatou");
+/// assert_eq!(transliterate("Hello", "en").unwrap(), "Hello");
+/// assert!(transliterate("مرحبا", "ar").is_err());
+/// ```
+///
+/// # Errors
+///
+/// This function will return `I18nError::UnsupportedLanguage` if the
+/// language has no transliteration table and the text is not already
+/// written in Latin characters.
+pub fn transliterate(
+    text: &str,
+    from_lang: &str,
+) -> Result<String, I18nError> {
+    let lang = from_lang.to_lowercase();
+    let primary = lang.split(|c| c == '-' || c == '_').next();
+
+    match primary {
+        Some("uk") => Ok(romanize_cyrillic(text, UKRAINIAN)),
+        Some("be") => Ok(romanize_cyrillic(text, BELARUSIAN)),
+        Some("bg") => Ok(romanize_cyrillic(text, BULGARIAN)),
+        Some("mk") => Ok(romanize_cyrillic(text, MACEDONIAN)),
+        Some("sr") => Ok(romanize_cyrillic(text, SERBIAN)),
+        Some(lang) if CYRILLIC_LANGUAGES.contains(&lang) => {
+            Ok(romanize_cyrillic(text, &[]))
+        }
+        Some("ja") => Ok(romanize_kana(text)),
+        _ if is_latin(text) => Ok(text.to_string()),
+        _ => Err(I18nError::UnsupportedLanguage(from_lang.to_string())),
+    }
+}
+
+/// Returns `true` if every letter of the text is written in Latin script.
+fn is_latin(text: &str) -> bool {
+    text.chars()
+        .filter(|c| c.is_alphabetic())
+        .all(|c| Script::of(c) == Some(Script::Latin))
+}
+
+/// Romanizes the Cyrillic letters of the text, looking letters up in
+/// `overrides` before the common table.
+fn romanize_cyrillic(
+    text: &str,
+    overrides: &[(char, &'static str)],
+) -> String {
+    let mut romanized = String::with_capacity(text.len());
+    for c in text.chars() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        match lookup(overrides, lower)
+            .or_else(|| lookup(CYRILLIC, lower))
+        {
+            Some(latin) if c != lower => {
+                let mut letters = latin.chars();
+                if let Some(first) = letters.next() {
+                    romanized.extend(first.to_uppercase());
+                    romanized.extend(letters);
+                }
+            }
+            Some(latin) => romanized.push_str(latin),
+            None => romanized.push(c),
+        }
+    }
+    romanized
+}
+
+/// Romanizes the hiragana and katakana of the text.
+fn romanize_kana(text: &str) -> String {
+    let mut romanized = String::with_capacity(text.len());
+    let mut chars = text.chars().map(to_hiragana).peekable();
+    let mut double_next = false;
+
+    while let Some(c) = chars.next() {
+        if c == SOKUON {
+            double_next = true;
+            continue;
+        }
+        if c == CHOONPU {
+            if let Some(vowel) = romanized.chars().last() {
+                if "aeiou".contains(vowel) {
+                    romanized.push(vowel);
+                }
+            }
+            continue;
+        }
+
+        let syllable = match lookup(KANA, c) {
+            Some(syllable) => {
+                match chars
+                    .peek()
+                    .and_then(|&next| combine(syllable, next))
+                {
+                    Some(combined) => {
+                        let _ = chars.next();
+                        combined
+                    }
+                    None => syllable.to_string(),
+                }
+            }
+            None => {
+                double_next = false;
+                romanized.push(c);
+                continue;
+            }
+        };
+
+        if double_next {
+            double_next = false;
+            if syllable.starts_with("ch") {
+                romanized.push('t');
+            } else if let Some(consonant) = syllable
+                .chars()
+                .next()
+                .filter(|c| !"aeioun".contains(*c))
+            {
+                romanized.push(consonant);
+            }
+        }
+        romanized.push_str(&syllable);
+    }
+    romanized
+}
+
+/// Combines an `i` syllable with a following small `ya`, `yu` or `yo`
+/// into a contracted syllable (`ki` + `ya` = `kya`, `shi` + `ya` = `sha`).
+fn combine(syllable: &str, next: char) -> Option<String> {
+    if !SMALL_Y.contains(&next) || syllable.len() < 2 {
+        return None;
+    }
+    let stem = syllable.strip_suffix('i')?;
+    let glide = lookup(KANA, next)?;
+    if stem.ends_with("sh")
+        || stem.ends_with("ch")
+        || stem.ends_with('j')
+    {
+        Some(format!("{}{}", stem, &glide[1..]))
+    } else {
+        Some(format!("{}{}", stem, glide))
+    }
+}
+
+/// Maps a katakana character onto the corresponding hiragana, leaving
+/// other characters unchanged.
+fn to_hiragana(c: char) -> char {
+    match c {
+        '\u{30A1}'..='\u{30F6}' => {
+            char::from_u32(c as u32 - 0x60).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// Looks up a character in a transliteration table.
+fn lookup(
+    table: &[(char, &'static str)],
+    c: char,
+) -> Option<&'static str> {
+    table.iter().find(|(from, _)| *from == c).map(|(_, to)| *to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cyrillic() {
+        assert_eq!(
+            transliterate("Щука и ёж", "ru").unwrap(),
+            "Shchuka i yozh"
+        );
+        assert_eq!(transliterate("Київ", "uk-UA").unwrap(), "Kyiv");
+        assert_eq!(transliterate("Харків", "uk").unwrap(), "Kharkiv");
+        assert_eq!(transliterate("Гора", "uk").unwrap(), "Hora");
+        assert_eq!(transliterate("Гора", "ru").unwrap(), "Gora");
+        assert_eq!(
+            transliterate("объём 42%", "RU").unwrap(),
+            "obyom 42%"
+        );
+        assert_eq!(
+            transliterate("България, щастие", "bg").unwrap(),
+            "Balgariya, shtastie"
+        );
+        assert_eq!(transliterate("Гродна", "be").unwrap(), "Hrodna");
+    }
+
+    #[test]
+    fn test_serbian_and_macedonian() {
+        assert_eq!(
+            transliterate("Љубљана", "sr").unwrap(),
+            "Ljubljana"
+        );
+        assert_eq!(
+            transliterate("Ђорђе Џаковић", "sr-RS").unwrap(),
+            "Đorđe Džaković"
+        );
+        assert_eq!(transliterate("Охрид", "mk").unwrap(), "Ohrid");
+        assert_eq!(
+            transliterate("Ѓорѓи ќе оди", "mk").unwrap(),
+            "Gjorgji kje odi"
+        );
+    }
+
+    #[test]
+    fn test_cyrillic_alphabets_are_romanized() {
+        let alphabets = [
+            ("be", "абвгдеёжзійклмнопрстуўфхцчшыьэюя"),
+            ("bg", "абвгдежзийклмнопрстуфхцчшщъьюя"),
+            ("mk", "абвгдѓежзѕијклљмнњопрстќуфхцчџш"),
+            ("ru", "абвгдеёжзийклмнопрстуфхцчшщъыьэюя"),
+            ("sr", "абвгдђежзијклљмнњопрстћуфхцчџш"),
+            ("uk", "абвгґдеєжзиіїйклмнопрстуфхцчшщьюя"),
+        ];
+        for (lang, alphabet) in alphabets {
+            assert!(CYRILLIC_LANGUAGES.contains(&lang));
+            for text in [alphabet.to_string(), alphabet.to_uppercase()]
+            {
+                let romanized = transliterate(&text, lang).unwrap();
+                assert!(
+                    is_latin(&romanized),
+                    "{}: {}",
+                    lang,
+                    romanized
+                );
+            }
+        }
+        assert_eq!(alphabets.len(), CYRILLIC_LANGUAGES.len());
+    }
+
+    #[test]
+    fn test_kana() {
+        assert_eq!(transliterate("きょうと", "ja").unwrap(), "kyouto");
+        assert_eq!(transliterate("しゃしん", "ja").unwrap(), "shashin");
+        assert_eq!(transliterate("きって", "ja").unwrap(), "kitte");
+        assert_eq!(transliterate("まっちゃ", "ja").unwrap(), "matcha");
+        assert_eq!(transliterate("コーヒー", "ja").unwrap(), "koohii");
+        assert_eq!(
+            transliterate("東京タワー", "ja").unwrap(),
+            "東京tawaa"
+        );
+    }
+
+    #[test]
+    fn test_latin_and_unsupported() {
+        assert_eq!(
+            transliterate("Crème brûlée", "fr").unwrap(),
+            "Crème brûlée"
+        );
+        assert_eq!(transliterate("Hello", "xx").unwrap(), "Hello");
+        assert_eq!(transliterate("Hello", "ru").unwrap(), "Hello");
+        assert!(matches!(
+            transliterate("你好", "zh"),
+            Err(I18nError::UnsupportedLanguage(lang)) if lang == "zh"
+        ));
+    }
+}
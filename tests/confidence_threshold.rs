@@ -0,0 +1,21 @@
+//! # Tests for the Default Confidence Threshold
+//!
+//! The default threshold is process-wide, so these tests live in their own
+//! test binary to avoid interfering with other tests.
+
+use langweave::language_detector::LanguageDetector;
+
+#[test]
+fn test_default_confidence_threshold() {
+    let detector = LanguageDetector::new();
+    let custom = LanguageDetector::new().with_confidence_threshold(0.2);
+    assert_eq!(detector.confidence_threshold(), 0.3);
+
+    LanguageDetector::set_default_confidence_threshold(0.8);
+    assert_eq!(detector.confidence_threshold(), 0.8);
+    assert_eq!(LanguageDetector::new().confidence_threshold(), 0.8);
+    assert_eq!(custom.confidence_threshold(), 0.2);
+
+    LanguageDetector::set_default_confidence_threshold(-1.0);
+    assert_eq!(detector.confidence_threshold(), 0.0);
+}
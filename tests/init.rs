@@ -0,0 +1,47 @@
+//! # Tests for Fallible Initialization
+//!
+//! Initialization replaces the process-wide translation store, so these
+//! tests live in their own test binary to avoid interfering with other
+//! tests.
+
+use langweave::error::I18nError;
+use langweave::translations::{
+    init, load_errors, set_locales_dir, translate,
+};
+use std::fs;
+
+#[test]
+fn test_init() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("fr.po"),
+        "msgid \"Hello\"\nmsgstr \"Salut\"\n",
+    )
+    .unwrap();
+
+    set_locales_dir(dir.path());
+    init().unwrap();
+    assert_eq!(translate("fr", "Hello").unwrap(), "Salut");
+    assert!(load_errors().is_empty());
+
+    // A broken catalog fails initialization and leaves the store as is
+    fs::write(
+        dir.path().join("fr.po"),
+        "msgid \"Hello\"\nmsgstr \"Allô\"\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("es.json"), "[1, 2]").unwrap();
+    let error = init().unwrap_err();
+    assert!(
+        matches!(&error, I18nError::LoadFailed { message, source: Some(_) }
+            if message.contains("es.json"))
+    );
+    assert_eq!(load_errors(), [error]);
+    assert_eq!(translate("fr", "Hello").unwrap(), "Salut");
+
+    set_locales_dir(dir.path().join("missing"));
+    assert!(matches!(
+        init(),
+        Err(I18nError::LoadFailed { source: None, .. })
+    ));
+}
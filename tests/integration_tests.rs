@@ -1,3 +1,7 @@
+//! # Integration Tests for LangWeave
+//!
+//! These tests exercise the public translation and detection APIs end to end.
+
 use langweave::error::I18nError;
 use langweave::translator::Translator;
 use langweave::{detect_language, translate};
@@ -0,0 +1,32 @@
+//! # Tests for a Custom Locales Directory
+//!
+//! The locales directory is process-wide, so these tests live in their own
+//! test binary to avoid interfering with other tests.
+
+use langweave::translations::{generation, set_locales_dir, translate};
+use std::fs;
+
+#[test]
+fn test_set_locales_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("fr.po"),
+        "msgid \"Hello\"\nmsgstr \"Salut\"\n",
+    )
+    .unwrap();
+
+    // Set before first use, the directory is loaded instead of `locales/`
+    set_locales_dir(dir.path());
+    assert_eq!(translate("fr", "Hello").unwrap(), "Salut");
+    assert!(translate("de", "Hello").is_err());
+
+    // Set after first use, the store is reloaded
+    let before = generation();
+    set_locales_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/locales"));
+    assert!(generation() > before);
+    assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+    assert_eq!(translate("de", "Hello").unwrap(), "Hallo");
+
+    set_locales_dir(dir.path().join("missing"));
+    assert!(translate("fr", "Hello").is_err());
+}
@@ -0,0 +1,30 @@
+//! # Tests for the `snapshot` Feature
+//!
+//! These tests replace the global translation store, so they live in their
+//! own test binary to avoid interfering with other tests.
+
+#![cfg(feature = "snapshot")]
+
+use langweave::error::I18nError;
+use langweave::translations::{
+    load_snapshot, remove_key, snapshot, translate, update_key,
+};
+
+#[test]
+fn test_snapshot_round_trip() {
+    update_key("x-snap", "greeting", "Hi");
+    let bytes = snapshot();
+
+    assert!(remove_key("x-snap", "greeting"));
+    assert!(translate("x-snap", "greeting").is_err());
+
+    load_snapshot(&bytes).unwrap();
+    assert_eq!(translate("x-snap", "greeting").unwrap(), "Hi");
+    assert_eq!(translate("de", "Hello").unwrap(), "Hallo");
+
+    assert!(matches!(
+        load_snapshot(b"not a snapshot"),
+        Err(I18nError::UnexpectedError(_))
+    ));
+    assert_eq!(translate("x-snap", "greeting").unwrap(), "Hi");
+}
@@ -0,0 +1,110 @@
+//! # Tests for Strict Mode
+//!
+//! Strict mode is a process-wide switch, so these tests live in their own
+//! test binary to avoid interfering with other tests.
+
+use langweave::caching_detector::CachingLanguageDetector;
+use langweave::error::I18nError;
+use langweave::language_detector::LanguageDetector;
+use langweave::language_detector_trait::LanguageDetectorTrait;
+use langweave::translations;
+use langweave::{
+    detect_comment_language, detect_direction, detect_language,
+    detect_with_declared, is_strict, localize_paragraphs, set_strict,
+    translate, Direction,
+};
+
+#[tokio::test]
+async fn test_strict_mode() {
+    assert!(!is_strict());
+    assert_eq!(detect_language("Привет мир").await.unwrap(), "ru");
+    assert_eq!(
+        translate("fr", "Untranslated").unwrap(),
+        "Untranslated"
+    );
+    assert_eq!(
+        detect_direction("مرحبا بالعالم").unwrap(),
+        Direction::Rtl
+    );
+    translations::update_key("x-strict", "Hello", "Hi");
+    assert_eq!(
+        translations::translate("x-strict", "Hello").unwrap(),
+        "Hi"
+    );
+    assert!(translations::key_availability("Hello")["x-strict"]);
+    translations::update_key("x-strict", "x-strict-only", "Only");
+    assert!(translations::coverage_report()["fr"]
+        .contains(&"x-strict-only".to_string()));
+    let caching =
+        CachingLanguageDetector::new(LanguageDetector::new(), 8);
+    assert_eq!(caching.detect("Привет мир").unwrap(), "ru");
+
+    set_strict(true);
+    assert!(matches!(
+        detect_direction("مرحبا بالعالم"),
+        Err(I18nError::UnsupportedLanguage(lang)) if lang == "ar"
+    ));
+    assert_eq!(
+        detect_direction("Bonjour le monde").unwrap(),
+        Direction::Ltr
+    );
+    let detector = LanguageDetector::new();
+    assert!(matches!(
+        detector.detect("Привет мир"),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+    assert!(detector
+        .detect_candidates("Hola amigo, bonjour", 5)
+        .unwrap()
+        .iter()
+        .all(|(lang, _)| lang == "fr"));
+    assert!(matches!(
+        translations::translate("x-strict", "Hello"),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+    let matrix =
+        translations::translate_matrix(&["fr"], &["Hello", "Missing"]);
+    assert_eq!(matrix["fr"].len(), 1);
+    let availability = translations::key_availability("Hello");
+    assert!(!availability.contains_key("x-strict"));
+    assert_eq!(availability.get("fr"), Some(&true));
+    let report = translations::coverage_report();
+    assert!(!report.contains_key("x-strict"));
+    assert!(!report["fr"].contains(&"x-strict-only".to_string()));
+    assert!(matches!(
+        caching.detect("Привет мир"),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+    assert_eq!(caching.detect("Bonjour le monde").unwrap(), "fr");
+    assert!(matches!(
+        detect_language("Привет мир").await,
+        Err(I18nError::UnsupportedLanguage(lang)) if lang == "ru"
+    ));
+    assert!(matches!(
+        detect_with_declared("Привет мир", Some("en")),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+    assert!(matches!(
+        detect_comment_language("// Привет мир"),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+    assert_eq!(localize_paragraphs("fr", &["Привет мир"])[0].0, "und");
+    assert!(matches!(
+        translate("fr", "Untranslated"),
+        Err(I18nError::TranslationFailed(_))
+    ));
+    assert!(matches!(
+        translate("xx", "Hello"),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+    assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+    assert_eq!(
+        detect_language("Bonjour le monde").await.unwrap(),
+        "fr"
+    );
+
+    set_strict(false);
+    assert_eq!(detect_language("Привет мир").await.unwrap(), "ru");
+    assert_eq!(caching.detect("Привет мир").unwrap(), "ru");
+    assert_eq!(caching.detect("Bonjour le monde").unwrap(), "fr");
+}
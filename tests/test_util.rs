@@ -0,0 +1,39 @@
+//! # Tests for the `test-util` Feature
+//!
+//! These tests reset the global translation store, so they live in their
+//! own test binary to avoid interfering with other tests.
+
+#![cfg(feature = "test-util")]
+
+use langweave::error::I18nError;
+use langweave::translations::{
+    clear, generation, reset_to_filesystem, translate, update_key,
+};
+use langweave::translator::Translator;
+use std::sync::Arc;
+
+#[test]
+fn test_clear_and_reset_to_filesystem() {
+    update_key("fr", "Hello", "Salut");
+    assert_eq!(translate("fr", "Hello").unwrap(), "Salut");
+    let cached = Translator::cached("de").unwrap();
+
+    let before = generation();
+    clear();
+    assert!(generation() > before);
+    assert!(matches!(
+        translate("fr", "Hello"),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+    assert!(matches!(
+        Translator::cached("de"),
+        Err(I18nError::UnsupportedLanguage(_))
+    ));
+
+    reset_to_filesystem();
+    assert_eq!(translate("fr", "Hello").unwrap(), "Bonjour");
+    assert_eq!(translate("de", "Hello").unwrap(), "Hallo");
+    let reloaded = Translator::cached("de").unwrap();
+    assert!(!Arc::ptr_eq(&cached, &reloaded));
+    assert!(Arc::ptr_eq(&reloaded, &Translator::cached("de").unwrap()));
+}